@@ -0,0 +1,431 @@
+//! The line-by-line text interface: turning what the player typed into
+//! moves and the engine's answers back into text.
+
+use std::fmt;
+use std::ops::Bound;
+
+use crate::daily::Date;
+use crate::hints::{Clue, ClueAnswer, DISTANCE_BUCKETS, Hint, HintMode};
+use crate::i18n::Messages;
+use crate::multi::Count;
+use crate::number::{self, NumberError};
+use crate::reverse::Contradiction;
+use crate::{Game, Outcome, SecretKind, words};
+
+/// A kind of secret the text interface can read guesses of and talk about.
+pub trait Guess: Ord + Clone + fmt::Display {
+    const KIND: SecretKind;
+
+    /// Why a line isn't a guess.
+    type Error: fmt::Debug + Clone + Copy + PartialEq + Eq;
+
+    fn read(line: &str) -> Result<Self, Self::Error>;
+
+    /// Catalog key of what to tell the player about a line that isn't a
+    /// guess.
+    fn invalid_key(err: Self::Error) -> &'static str;
+
+    /// How a transcript notes a line that isn't a guess.
+    fn invalid_tag(err: Self::Error) -> &'static str;
+
+    /// The closest possible secrets after and before this one, used to say
+    /// which guesses are still open. Only called where one exists.
+    fn next(&self) -> Self;
+    fn previous(&self) -> Self;
+
+    /// How many possible secrets apart this and `other` are.
+    fn distance(&self, other: &Self) -> u64;
+
+    /// Extra hints about the latest guess. Any kind of secret can tell the
+    /// player the interval left and whether they're getting warmer.
+    fn hints(game: &Game<Self>, messages: &Messages) -> Vec<String> {
+        let Some((guess, earlier)) = game.guesses().split_last() else {
+            return Vec::new();
+        };
+        let secret = game.secret();
+        if *guess == secret {
+            return Vec::new();
+        }
+
+        let mut hints = Vec::new();
+        for mode in game.hint_modes() {
+            match mode {
+                HintMode::WarmerColder => {
+                    if let Some(previous) = earlier.last() {
+                        let closer =
+                            Hint::closer(guess.distance(&secret), previous.distance(&secret));
+                        hints.push(hint(closer, messages));
+                    }
+                }
+                HintMode::Interval => {
+                    let (low, high) = still_open(game);
+                    hints.push(messages.format("hint_interval", &[("low", &low), ("high", &high)]));
+                }
+                HintMode::Distance | HintMode::Clues => {}
+            }
+        }
+        hints
+    }
+
+    /// Buys `clue` if this game sells them.
+    fn buy_clue(_game: &mut Game<Self>, _clue: Clue) -> Option<ClueAnswer> {
+        None
+    }
+}
+
+impl Guess for u64 {
+    const KIND: SecretKind = SecretKind::Number;
+    type Error = NumberError;
+
+    fn read(line: &str) -> Result<Self, Self::Error> {
+        number::parse(line)
+    }
+
+    fn invalid_key(err: NumberError) -> &'static str {
+        match err {
+            NumberError::Empty | NumberError::NotANumber => "invalid_number",
+            NumberError::Negative => "invalid_negative",
+            NumberError::Overflow => "invalid_overflow",
+            NumberError::Fractional => "invalid_fractional",
+        }
+    }
+
+    fn invalid_tag(err: NumberError) -> &'static str {
+        match err {
+            NumberError::Empty => "invalid empty",
+            NumberError::Negative => "invalid negative",
+            NumberError::Overflow => "invalid overflow",
+            NumberError::Fractional => "invalid fractional",
+            NumberError::NotANumber => "invalid",
+        }
+    }
+
+    fn next(&self) -> Self {
+        self + 1
+    }
+
+    fn previous(&self) -> Self {
+        self - 1
+    }
+
+    fn distance(&self, other: &Self) -> u64 {
+        self.abs_diff(*other)
+    }
+
+    fn hints(game: &Game, messages: &Messages) -> Vec<String> {
+        game.hints()
+            .into_iter()
+            .map(|h| hint(h, messages))
+            .collect()
+    }
+
+    fn buy_clue(game: &mut Game, clue: Clue) -> Option<ClueAnswer> {
+        game.buy_clue(clue)
+    }
+}
+
+/// A line that isn't one of the words or dates a game knows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Unrecognized;
+
+/// Words from the bundled list, in dictionary order.
+impl Guess for &'static str {
+    const KIND: SecretKind = SecretKind::Word;
+    type Error = Unrecognized;
+
+    fn read(line: &str) -> Result<Self, Unrecognized> {
+        words::lookup(line).ok_or(Unrecognized)
+    }
+
+    fn invalid_key(_: Unrecognized) -> &'static str {
+        "invalid_word"
+    }
+
+    fn invalid_tag(_: Unrecognized) -> &'static str {
+        "invalid"
+    }
+
+    fn next(&self) -> Self {
+        words::next(self).expect("a word before the secret has a next word")
+    }
+
+    fn previous(&self) -> Self {
+        words::previous(self).expect("a word after the secret has a previous word")
+    }
+
+    fn distance(&self, other: &Self) -> u64 {
+        let position = |word| words::position(word).expect("guesses are listed words");
+        position(self).abs_diff(position(other)) as u64
+    }
+}
+
+impl Guess for Date {
+    const KIND: SecretKind = SecretKind::Date;
+    type Error = Unrecognized;
+
+    fn read(line: &str) -> Result<Self, Unrecognized> {
+        line.trim().parse().map_err(|_| Unrecognized)
+    }
+
+    fn invalid_key(_: Unrecognized) -> &'static str {
+        "invalid_date"
+    }
+
+    fn invalid_tag(_: Unrecognized) -> &'static str {
+        "invalid"
+    }
+
+    fn next(&self) -> Self {
+        Date::from_unix_days(self.unix_days() + 1)
+    }
+
+    fn previous(&self) -> Self {
+        Date::from_unix_days(self.unix_days() - 1)
+    }
+
+    fn distance(&self, other: &Self) -> u64 {
+        self.unix_days().abs_diff(other.unix_days())
+    }
+}
+
+/// Messages that name the kind of secret come in one variant per kind,
+/// such as `too_small` for numbers and `too_small_word` for words.
+pub fn kind_key<T: Guess>(key: &str) -> String {
+    match T::KIND {
+        SecretKind::Number => key.to_string(),
+        kind => format!("{key}_{kind}"),
+    }
+}
+
+/// What one line of player input means.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Input<T: Guess = u64> {
+    Guess(T),
+    Clue(Clue),
+    Quit,
+    Invalid(T::Error),
+}
+
+impl Input {
+    /// Reads a line of a numeric game.
+    pub fn parse(line: &str) -> Input {
+        Input::read(line)
+    }
+}
+
+impl<T: Guess> Input<T> {
+    pub fn read(line: &str) -> Input<T> {
+        let line = line.trim();
+        if is_quit_command(line) {
+            return Input::Quit;
+        }
+        let err = match T::read(line) {
+            Ok(guess) => return Input::Guess(guess),
+            Err(err) => err,
+        };
+        // Clues are all about numbers.
+        match line.parse() {
+            Ok(clue) if T::KIND == SecretKind::Number => Input::Clue(clue),
+            _ => Input::Invalid(err),
+        }
+    }
+}
+
+impl<T: Guess> fmt::Display for Input<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Input::Guess(guess) => write!(f, "guess {guess}"),
+            Input::Clue(Clue::Parity) => write!(f, "clue parity"),
+            Input::Clue(Clue::DivisibleBy(n)) => write!(f, "clue div {n}"),
+            Input::Quit => write!(f, "quit"),
+            Input::Invalid(err) => f.write_str(T::invalid_tag(*err)),
+        }
+    }
+}
+
+pub fn is_quit_command(input: &str) -> bool {
+    ["quit", "q", "exit"]
+        .iter()
+        .any(|command| input.eq_ignore_ascii_case(command))
+}
+
+/// Whether `input` asks to save the game and finish it later.
+pub fn is_save_command(input: &str) -> bool {
+    input.eq_ignore_ascii_case("save")
+}
+
+/// Everything printed in answer to one line of input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response<T: Guess = u64> {
+    pub input: Input<T>,
+    pub lines: Vec<String>,
+}
+
+/// Applies one line of input to `game` and returns the text to show for it.
+pub fn respond<T: Guess>(game: &mut Game<T>, line: &str, messages: &Messages) -> Response<T> {
+    let input = Input::<T>::read(line);
+    let mut lines = Vec::new();
+
+    match &input {
+        Input::Quit => lines.push(give_up(game, messages)),
+        Input::Invalid(err) => lines.push(messages.text(T::invalid_key(*err)).to_string()),
+        Input::Clue(clue) => match T::buy_clue(game, *clue) {
+            Some(answer) => lines.push(
+                messages.format("clue_bought", &[("answer", &clue_answer(answer, messages))]),
+            ),
+            None => lines.push(messages.text("clues_off").to_string()),
+        },
+        Input::Guess(guess) => {
+            let outcome = game.guess(guess.clone());
+            lines.push(describe(game, guess.clone(), outcome, messages));
+            if matches!(outcome, Outcome::TooSmall | Outcome::TooBig) {
+                lines.extend(T::hints(game, messages));
+            }
+        }
+    }
+
+    if input != Input::Quit && game.is_lost() {
+        lines.push(lose(game, messages));
+    }
+    Response { input, lines }
+}
+
+/// What to tell the player about a line that isn't a number.
+pub fn invalid(err: NumberError, messages: &Messages) -> &'static str {
+    messages.text(u64::invalid_key(err))
+}
+
+pub fn describe<T: Guess>(
+    game: &Game<T>,
+    guess: T,
+    outcome: Outcome,
+    messages: &Messages,
+) -> String {
+    match outcome {
+        Outcome::TooSmall => messages.text(&kind_key::<T>("too_small")).to_string(),
+        Outcome::TooBig => messages.text(&kind_key::<T>("too_big")).to_string(),
+        Outcome::OutOfRange => messages.format(
+            "out_of_range",
+            &[
+                ("guess", &guess),
+                ("min", game.range().start()),
+                ("max", game.range().end()),
+            ],
+        ),
+        Outcome::RuledOut => {
+            let (low, high) = still_open(game);
+            messages.format(
+                &kind_key::<T>("ruled_out"),
+                &[("guess", &guess), ("low", &low), ("high", &high)],
+            )
+        }
+        Outcome::Correct => messages.text("you_win").to_string(),
+        Outcome::GameOver => messages.text("game_over").to_string(),
+    }
+}
+
+/// The first and last guesses that could still be the secret.
+fn still_open<T: Guess>(game: &Game<T>) -> (T, T) {
+    let (low, high) = game.bounds();
+    let low = match low {
+        Bound::Included(low) => low.clone(),
+        Bound::Excluded(above) => above.next(),
+        Bound::Unbounded => unreachable!("games are always bounded"),
+    };
+    let high = match high {
+        Bound::Included(high) => high.clone(),
+        Bound::Excluded(below) => below.previous(),
+        Bound::Unbounded => unreachable!("games are always bounded"),
+    };
+    (low, high)
+}
+
+pub fn hint(hint: Hint, messages: &Messages) -> String {
+    match hint {
+        Hint::Warmer => messages.text("hint_warmer").to_string(),
+        Hint::Colder => messages.text("hint_colder").to_string(),
+        Hint::SameDistance => messages.text("hint_same_distance").to_string(),
+        Hint::Within(bucket) => messages.format("hint_within", &[("distance", &bucket)]),
+        Hint::FarAway => {
+            let largest = DISTANCE_BUCKETS[DISTANCE_BUCKETS.len() - 1];
+            messages.format("hint_far_away", &[("distance", &largest)])
+        }
+        Hint::Interval(low, high) => {
+            messages.format("hint_interval", &[("low", &low), ("high", &high)])
+        }
+    }
+}
+
+pub fn clue_answer(answer: ClueAnswer, messages: &Messages) -> String {
+    match answer {
+        ClueAnswer::Even(true) => messages.text("clue_even").to_string(),
+        ClueAnswer::Even(false) => messages.text("clue_odd").to_string(),
+        ClueAnswer::DivisibleBy(n, true) => messages.format("clue_divisible", &[("divisor", &n)]),
+        ClueAnswer::DivisibleBy(n, false) => {
+            messages.format("clue_not_divisible", &[("divisor", &n)])
+        }
+    }
+}
+
+pub fn give_up<T: Guess>(game: &Game<T>, messages: &Messages) -> String {
+    messages.format(&kind_key::<T>("give_up"), &[("secret", &game.secret())])
+}
+
+pub fn lose<T: Guess>(game: &Game<T>, messages: &Messages) -> String {
+    let key = if game.is_expired() {
+        "lose_time"
+    } else {
+        "lose_attempts"
+    };
+    messages.format(&kind_key::<T>(key), &[("secret", &game.secret())])
+}
+
+/// Owns up to the lies told in Ulam mode. `None` for honest games.
+pub fn lies<T: Guess>(game: &Game<T>, messages: &Messages) -> Option<String> {
+    game.max_lies()?;
+    if game.lies().is_empty() {
+        return Some(messages.text("summary_no_lies").to_string());
+    }
+    let guesses: Vec<String> = game
+        .lies()
+        .iter()
+        .map(|&i| game.guesses()[i].to_string())
+        .collect();
+    Some(messages.format(
+        "summary_lies",
+        &[
+            ("count", &game.lies().len()),
+            ("guesses", &guesses.join(", ")),
+        ],
+    ))
+}
+
+/// How many secrets lie on each side of a guess with several secrets.
+pub fn count(count: Count, messages: &Messages) -> String {
+    messages.format(
+        "multi_count",
+        &[
+            ("below", &count.below),
+            ("equal", &count.equal),
+            ("above", &count.above),
+        ],
+    )
+}
+
+/// Why the player's answers can't all be true.
+pub fn contradiction(contradiction: Contradiction, messages: &Messages) -> String {
+    let (min, max) = contradiction.range;
+    match (contradiction.higher_than, contradiction.lower_than) {
+        (Some(high), _) if high >= max => messages.format(
+            "contradiction_above_max",
+            &[("guess", &high), ("max", &max)],
+        ),
+        (_, Some(low)) if low <= min => {
+            messages.format("contradiction_below_min", &[("guess", &low), ("min", &min)])
+        }
+        (Some(high), Some(low)) => {
+            messages.format("contradiction_no_gap", &[("low", &high), ("high", &low)])
+        }
+        _ => messages.text("contradiction_nothing_left").to_string(),
+    }
+}
@@ -0,0 +1,183 @@
+//! The daily challenge: one secret per calendar day, the same for everyone.
+
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::i18n::Messages;
+use crate::{Game, SecretSource};
+
+/// Mixed into every date before hashing, so the daily secrets can't be read
+/// off any other seed-based game.
+const SALT: &str = "guessing_game daily v1";
+
+/// A day in the proleptic Gregorian calendar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    year: i32,
+    month: u32,
+    day: u32,
+}
+
+impl Date {
+    /// Panics if `month` or `day` is out of range for that month.
+    pub fn new(year: i32, month: u32, day: u32) -> Date {
+        assert!(
+            is_date(year, month, day),
+            "{year}-{month}-{day} is not a date"
+        );
+        Date { year, month, day }
+    }
+
+    /// Today in UTC, so the whole team rolls over to a new puzzle together.
+    pub fn today() -> Date {
+        let seconds = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        Date::from_unix_days((seconds / 86_400) as i64)
+    }
+
+    /// The date `days` after 1970-01-01.
+    pub fn from_unix_days(days: i64) -> Date {
+        // Howard Hinnant's civil_from_days, counting from 0000-03-01 so leap
+        // days fall at the end of each year.
+        let days = days + 719_468;
+        let era = days.div_euclid(146_097);
+        let day_of_era = days.rem_euclid(146_097);
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let shifted_month = (5 * day_of_year + 2) / 153;
+        let day = (day_of_year - (153 * shifted_month + 2) / 5 + 1) as u32;
+        let month = if shifted_month < 10 {
+            shifted_month + 3
+        } else {
+            shifted_month - 9
+        } as u32;
+        let year = year_of_era + era * 400 + i64::from(month <= 2);
+        Date::new(year as i32, month, day)
+    }
+
+    /// Days since 1970-01-01, the inverse of `from_unix_days`.
+    pub fn unix_days(self) -> i64 {
+        // Howard Hinnant's days_from_civil.
+        let year = i64::from(self.year) - i64::from(self.month <= 2);
+        let era = year.div_euclid(400);
+        let year_of_era = year.rem_euclid(400);
+        let shifted_month = i64::from((self.month + 9) % 12);
+        let day_of_year = (153 * shifted_month + 2) / 5 + i64::from(self.day) - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        era * 146_097 + day_of_era - 719_468
+    }
+
+    pub fn year(self) -> i32 {
+        self.year
+    }
+
+    /// The seed behind this date's secret, from a salted FNV-1a hash of the
+    /// ISO date.
+    pub fn seed(self) -> u64 {
+        const OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+        const PRIME: u64 = 0x0100_0000_01b3;
+
+        format!("{SALT}:{self}").bytes().fold(OFFSET, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(PRIME)
+        })
+    }
+}
+
+fn is_date(year: i32, month: u32, day: u32) -> bool {
+    let days_in_month = match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        1..=12 => 31,
+        _ => return false,
+    };
+    (1..=days_in_month).contains(&day)
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+impl FromStr for Date {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("'{s}' is not a date (expected YYYY-MM-DD)");
+
+        let mut parts = s.splitn(3, '-');
+        let (Some(year), Some(month), Some(day)) = (parts.next(), parts.next(), parts.next())
+        else {
+            return Err(invalid());
+        };
+        match (year.parse(), month.parse(), day.parse()) {
+            (Ok(year), Ok(month), Ok(day)) if is_date(year, month, day) => {
+                Ok(Date { year, month, day })
+            }
+            _ => Err(invalid()),
+        }
+    }
+}
+
+/// The secret for one day. Reports the date's seed, so `--seed` replays it.
+pub struct DailySecret {
+    date: Date,
+}
+
+impl DailySecret {
+    pub fn new(date: Date) -> Self {
+        Self { date }
+    }
+}
+
+impl SecretSource for DailySecret {
+    fn pick(&mut self, range: &RangeInclusive<u64>) -> u64 {
+        // Drawn exactly as `SeededSecret` draws its first secret.
+        ChaCha8Rng::seed_from_u64(self.date.seed()).random_range(range.clone())
+    }
+
+    fn seed(&self) -> Option<u64> {
+        Some(self.date.seed())
+    }
+}
+
+/// A result to paste into chat without giving the secret away: the date, the
+/// score and one square per guess showing which way it was off.
+///
+/// ```text
+/// Guessing Game 2026-10-17 4/8
+/// 🔼🔽🔼🟩
+/// ```
+pub fn share(game: &Game, date: Date, messages: &Messages) -> String {
+    let score = if game.is_won() {
+        game.attempts().to_string()
+    } else {
+        "X".to_string()
+    };
+    let score = match game.max_attempts() {
+        Some(max) => format!("{score}/{max}"),
+        None => score,
+    };
+
+    let squares: String = game
+        .guesses()
+        .iter()
+        .map(|guess| match guess.cmp(&game.secret()) {
+            Ordering::Less => '🔼',
+            Ordering::Greater => '🔽',
+            Ordering::Equal => '🟩',
+        })
+        .collect();
+
+    let title = messages.format("share_title", &[("date", &date), ("score", &score)]);
+    format!("{title}\n{squares}")
+}
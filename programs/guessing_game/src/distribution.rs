@@ -0,0 +1,320 @@
+//! Secrets that aren't equally likely, and a bot that knows it.
+
+use rand::distr::Distribution as _;
+use rand::distr::weighted::WeightedIndex;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io::{self, BufRead};
+use std::ops::RangeInclusive;
+use std::rc::Rc;
+use std::str::FromStr;
+
+use crate::solver::{Interval, Solver};
+use crate::{Outcome, SecretSource};
+
+/// Largest range a non-uniform distribution can cover, as it keeps a weight
+/// for every number.
+pub const MAX_LEN: u64 = 1_000_000;
+
+/// How likely each number in the range is to be the secret.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum Distribution {
+    /// Every number equally likely.
+    #[default]
+    Uniform,
+    /// A bell curve centred on the range, a sixth of it wide either side.
+    Normal,
+    /// Skewed towards the low end: each step up is a little less likely,
+    /// the top of the range about 150 times less than the bottom.
+    Exponential,
+    /// Weights read from a file; numbers without one can't be the secret.
+    Weights(BTreeMap<u64, f64>),
+}
+
+impl Distribution {
+    pub const NAMED: [Distribution; 3] = [
+        Distribution::Uniform,
+        Distribution::Normal,
+        Distribution::Exponential,
+    ];
+
+    /// Reads one `<number> <weight>` pair per line, skipping blank lines and
+    /// `#` comments. Weights are relative and needn't add up to anything.
+    pub fn load(input: impl BufRead) -> io::Result<Distribution> {
+        let mut weights = BTreeMap::new();
+
+        for (index, line) in input.lines().enumerate() {
+            let line = line?;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let invalid = |message: String| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("line {}: {message}", index + 1),
+                )
+            };
+
+            let mut fields = line.split_whitespace();
+            let (Some(number), Some(weight), None) = (fields.next(), fields.next(), fields.next())
+            else {
+                return Err(invalid(format!(
+                    "expected '<number> <weight>', found '{line}'"
+                )));
+            };
+            let number: u64 = number
+                .parse()
+                .map_err(|_| invalid(format!("'{number}' is not a number")))?;
+            let weight = match weight.parse::<f64>() {
+                Ok(weight) if weight.is_finite() && weight >= 0.0 => weight,
+                _ => {
+                    return Err(invalid(format!(
+                        "'{weight}' is not a weight of zero or more"
+                    )));
+                }
+            };
+            if weights.insert(number, weight).is_some() {
+                return Err(invalid(format!("{number} is weighted twice")));
+            }
+        }
+
+        Ok(Distribution::Weights(weights))
+    }
+
+    /// Checks the distribution can pick a secret from `range`.
+    pub fn check(&self, range: &RangeInclusive<u64>) -> Result<(), String> {
+        if *self == Distribution::Uniform {
+            return Ok(());
+        }
+        if range.end() - range.start() >= MAX_LEN {
+            return Err(format!(
+                "the {self} distribution only covers ranges of up to {MAX_LEN} numbers"
+            ));
+        }
+        if let Distribution::Weights(weights) = self {
+            if let Some(number) = weights.keys().find(|number| !range.contains(number)) {
+                return Err(format!(
+                    "weighted number {number} is outside {}..={}",
+                    range.start(),
+                    range.end()
+                ));
+            }
+            if weights.values().all(|&weight| weight == 0.0) {
+                return Err("every weight is zero".to_string());
+            }
+        }
+        Ok(())
+    }
+
+    /// The relative weight of every number in `range`, lowest first, or
+    /// `None` for the uniform distribution. Panics unless `check` passes.
+    pub fn weights(&self, range: &RangeInclusive<u64>) -> Option<Vec<f64>> {
+        if let Err(err) = self.check(range) {
+            panic!("{err}");
+        }
+
+        if *self == Distribution::Uniform {
+            return None;
+        }
+
+        let (start, end) = (*range.start(), *range.end());
+        let len = (u128::from(end - start) + 1) as f64;
+        let weights = match self {
+            Distribution::Uniform => unreachable!("uniform weights are never listed"),
+            Distribution::Normal => {
+                let centre = (start as f64 + end as f64) / 2.0;
+                let spread = len / 6.0;
+                (start..=end)
+                    .map(|n| (-0.5 * ((n as f64 - centre) / spread).powi(2)).exp())
+                    .collect()
+            }
+            Distribution::Exponential => {
+                let rate = 5.0 / len;
+                (0..=end - start)
+                    .map(|i| (-rate * i as f64).exp())
+                    .collect()
+            }
+            Distribution::Weights(weights) => (start..=end)
+                .map(|n| weights.get(&n).copied().unwrap_or(0.0))
+                .collect(),
+        };
+        Some(weights)
+    }
+}
+
+impl fmt::Display for Distribution {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Distribution::Uniform => "uniform",
+            Distribution::Normal => "normal",
+            Distribution::Exponential => "exponential",
+            Distribution::Weights(_) => "weighted",
+        };
+        f.write_str(name)
+    }
+}
+
+impl FromStr for Distribution {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "uniform" => Ok(Distribution::Uniform),
+            "normal" => Ok(Distribution::Normal),
+            "exponential" | "skewed" => Ok(Distribution::Exponential),
+            _ => Err(format!(
+                "unknown distribution '{s}' (expected uniform, normal or exponential)"
+            )),
+        }
+    }
+}
+
+/// Seeded secrets drawn from a distribution. Uniform secrets are the same
+/// ones `SeededSecret` picks for the same seed.
+pub struct DistributedSecret {
+    distribution: Distribution,
+    /// Weights for the range last picked from, which rarely changes.
+    index: Option<(RangeInclusive<u64>, WeightedIndex<f64>)>,
+    next_seed: u64,
+    last_seed: Option<u64>,
+    seeds: ChaCha8Rng,
+}
+
+impl DistributedSecret {
+    pub fn new(distribution: Distribution, seed: u64) -> Self {
+        Self {
+            distribution,
+            index: None,
+            next_seed: seed,
+            last_seed: None,
+            seeds: ChaCha8Rng::seed_from_u64(seed),
+        }
+    }
+}
+
+impl SecretSource for DistributedSecret {
+    fn pick(&mut self, range: &RangeInclusive<u64>) -> u64 {
+        let seed = self.next_seed;
+        self.next_seed = self.seeds.random();
+        self.last_seed = Some(seed);
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+
+        if self
+            .index
+            .as_ref()
+            .is_none_or(|(cached, _)| cached != range)
+        {
+            self.index = self.distribution.weights(range).map(|weights| {
+                let index = WeightedIndex::new(weights).expect("checked weights are valid");
+                (range.clone(), index)
+            });
+        }
+        match &self.index {
+            Some((_, index)) => range.start() + index.sample(&mut rng) as u64,
+            None => rng.random_range(range.clone()),
+        }
+    }
+
+    fn seed(&self) -> Option<u64> {
+        self.last_seed
+    }
+}
+
+/// Running totals of a distribution's weights over a range, built once and
+/// shared by every [`EntropySolver`] that plays the range.
+#[derive(Debug, Clone)]
+pub struct CumulativeWeights {
+    start: u64,
+    /// `totals[i]` is the total weight of the first `i` numbers; `None` for
+    /// uniform weights.
+    totals: Option<Rc<[f64]>>,
+}
+
+impl CumulativeWeights {
+    /// Panics unless `distribution.check(range)` passes.
+    pub fn new(range: &RangeInclusive<u64>, distribution: &Distribution) -> Self {
+        let totals = distribution.weights(range).map(|weights| {
+            let mut total = 0.0;
+            let mut totals = vec![0.0];
+            totals.extend(weights.iter().map(|weight| {
+                total += weight;
+                total
+            }));
+            totals.into()
+        });
+
+        Self {
+            start: *range.start(),
+            totals,
+        }
+    }
+}
+
+/// Picks the guess whose answer is hardest to predict.
+///
+/// Each guess splits the remaining probability into "too small", "correct"
+/// and "too big", and every answer tells the most about the secret when
+/// those are as close to even as they can be. That's the weighted median:
+/// with uniform weights it is the midpoint, as in binary search; with skewed
+/// weights it leans towards the likely numbers, which are then found in
+/// fewer guesses on average. The price is a longer worst case, so under a
+/// tight attempt limit the odd unlikely secret is missed.
+pub struct EntropySolver {
+    interval: Interval,
+    weights: CumulativeWeights,
+}
+
+impl EntropySolver {
+    /// Panics unless `distribution.check(&range)` passes.
+    pub fn new(range: RangeInclusive<u64>, distribution: &Distribution) -> Self {
+        let weights = CumulativeWeights::new(&range, distribution);
+        Self::with_weights(range, &weights)
+    }
+
+    /// Plays `range` with weights already added up, so many games can share
+    /// them. `weights` must have been built for `range`.
+    pub fn with_weights(range: RangeInclusive<u64>, weights: &CumulativeWeights) -> Self {
+        assert_eq!(
+            weights.start,
+            *range.start(),
+            "the weights are for another range"
+        );
+        Self {
+            interval: Interval::new(&range),
+            weights: weights.clone(),
+        }
+    }
+}
+
+impl Solver for EntropySolver {
+    fn next_guess(&mut self) -> Option<u64> {
+        let (low, high) = self.interval.get()?.into_inner();
+        let midpoint = low + (high - low) / 2;
+        let Some(totals) = &self.weights.totals else {
+            return Some(midpoint);
+        };
+
+        let start = self.weights.start;
+        let (low, high) = ((low - start) as usize, (high - start) as usize);
+        let total = totals[high + 1] - totals[low];
+        if total <= 0.0 {
+            // The weights say the secret can't be here; it is anyway.
+            return Some(midpoint);
+        }
+
+        // The first guess that takes the weight up to it past half. Its own
+        // weight is never zero, so stretches the weights rule out are only
+        // ever skipped over.
+        let half = totals[low] + total / 2.0;
+        let median = low + totals[low + 1..=high + 1].partition_point(|&sum| sum < half);
+        Some(start + median.min(high) as u64)
+    }
+
+    fn observe(&mut self, guess: u64, outcome: Outcome) {
+        self.interval.narrow(guess, outcome);
+    }
+}
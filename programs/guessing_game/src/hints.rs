@@ -0,0 +1,142 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+/// Optional extra feedback on top of "Too small!" / "Too big!".
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HintMode {
+    /// Whether a guess is closer to the secret than the previous one.
+    #[serde(rename = "warmer")]
+    WarmerColder,
+    /// How far off a guess is, rounded up to a bucket like "within 5".
+    Distance,
+    /// The interval the secret is still known to be in.
+    Interval,
+    /// Parity and divisibility questions, each costing an attempt.
+    Clues,
+}
+
+impl HintMode {
+    pub const ALL: [HintMode; 4] = [
+        HintMode::WarmerColder,
+        HintMode::Distance,
+        HintMode::Interval,
+        HintMode::Clues,
+    ];
+}
+
+impl fmt::Display for HintMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            HintMode::WarmerColder => "warmer",
+            HintMode::Distance => "distance",
+            HintMode::Interval => "interval",
+            HintMode::Clues => "clues",
+        };
+        f.write_str(name)
+    }
+}
+
+impl FromStr for HintMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "warmer" | "colder" | "warmer-colder" => Ok(HintMode::WarmerColder),
+            "distance" => Ok(HintMode::Distance),
+            "interval" => Ok(HintMode::Interval),
+            "clues" => Ok(HintMode::Clues),
+            _ => Err(format!(
+                "unknown hint mode '{s}' (expected warmer, distance, interval or clues)"
+            )),
+        }
+    }
+}
+
+/// Distance buckets reported by `HintMode::Distance`.
+pub const DISTANCE_BUCKETS: [u64; 8] = [1, 2, 5, 10, 25, 50, 100, 1000];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hint {
+    Warmer,
+    Colder,
+    /// Exactly as far away as the previous guess, just on the other side.
+    SameDistance,
+    /// The secret is at most this far from the guess.
+    Within(u64),
+    /// The secret is further away than the largest bucket.
+    FarAway,
+    Interval(u64, u64),
+}
+
+impl Hint {
+    pub fn warmer_colder(secret: u64, guess: u64, previous: u64) -> Hint {
+        Hint::closer(guess.abs_diff(secret), previous.abs_diff(secret))
+    }
+
+    /// Warmer or colder, going by how far the latest and previous guesses
+    /// were from the secret.
+    pub fn closer(now: u64, before: u64) -> Hint {
+        match now.cmp(&before) {
+            Ordering::Less => Hint::Warmer,
+            Ordering::Greater => Hint::Colder,
+            Ordering::Equal => Hint::SameDistance,
+        }
+    }
+
+    pub fn distance(secret: u64, guess: u64) -> Hint {
+        let distance = guess.abs_diff(secret);
+        DISTANCE_BUCKETS
+            .iter()
+            .find(|&&bucket| distance <= bucket)
+            .map_or(Hint::FarAway, |&bucket| Hint::Within(bucket))
+    }
+}
+
+/// A question about the secret that costs one attempt to ask.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Clue {
+    Parity,
+    DivisibleBy(u64),
+}
+
+impl Clue {
+    pub fn answer(self, secret: u64) -> ClueAnswer {
+        match self {
+            Clue::Parity => ClueAnswer::Even(secret.is_multiple_of(2)),
+            Clue::DivisibleBy(n) => ClueAnswer::DivisibleBy(n, secret.is_multiple_of(n)),
+        }
+    }
+}
+
+impl FromStr for Clue {
+    type Err = String;
+
+    /// Accepts `parity`, `even`, `odd`, or `div N` / `divisible N`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut words = s.split_whitespace();
+        let command = words.next().unwrap_or_default().to_ascii_lowercase();
+        let argument = words.next();
+        if words.next().is_some() {
+            return Err(format!("too many words in clue '{s}'"));
+        }
+
+        match (command.as_str(), argument) {
+            ("parity" | "even" | "odd", None) => Ok(Clue::Parity),
+            ("div" | "divisible", Some(n)) => match n.parse() {
+                Ok(0) | Err(_) => Err(format!("'{n}' is not a positive divisor")),
+                Ok(n) => Ok(Clue::DivisibleBy(n)),
+            },
+            _ => Err(format!("unknown clue '{s}' (try 'parity' or 'div 3')")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClueAnswer {
+    Even(bool),
+    DivisibleBy(u64, bool),
+}
@@ -0,0 +1,116 @@
+use crate::hints::{Clue, ClueAnswer};
+use crate::{Game, Outcome};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Player {
+    pub name: String,
+    pub attempts: u32,
+}
+
+/// Several players taking turns at the same secret on one terminal.
+///
+/// Attempt limits apply per player: a player who has used them all sits out
+/// and the game is lost once nobody is left to guess.
+#[derive(Debug)]
+pub struct HotSeat {
+    game: Game,
+    players: Vec<Player>,
+    max_attempts: Option<u32>,
+    turn: usize,
+    winner: Option<usize>,
+}
+
+impl HotSeat {
+    /// `game` should not have its own attempt limit, pass it here instead.
+    ///
+    /// Panics if `names` is empty.
+    pub fn new(game: Game, names: Vec<String>, max_attempts: Option<u32>) -> Self {
+        assert!(!names.is_empty(), "hot-seat needs at least one guesser");
+
+        let players = names
+            .into_iter()
+            .map(|name| Player { name, attempts: 0 })
+            .collect();
+        Self {
+            game,
+            players,
+            max_attempts,
+            turn: 0,
+            winner: None,
+        }
+    }
+
+    pub fn game(&self) -> &Game {
+        &self.game
+    }
+
+    pub fn players(&self) -> &[Player] {
+        &self.players
+    }
+
+    /// The player whose turn it is.
+    pub fn current(&self) -> &Player {
+        &self.players[self.turn]
+    }
+
+    pub fn remaining_attempts(&self) -> Option<u32> {
+        self.max_attempts
+            .map(|max| max.saturating_sub(self.current().attempts))
+    }
+
+    pub fn winner(&self) -> Option<&Player> {
+        self.winner.map(|index| &self.players[index])
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.winner.is_some() || self.players.iter().all(|p| !self.can_guess(p))
+    }
+
+    /// Takes the current player's guess and passes the turn on unless they
+    /// won. Guesses that don't cost an attempt keep the same player up.
+    pub fn guess(&mut self, guess: u64) -> Outcome {
+        if self.is_over() {
+            return Outcome::GameOver;
+        }
+
+        let before = self.game.attempts();
+        let outcome = self.game.guess(guess);
+        if self.game.attempts() == before {
+            return outcome;
+        }
+
+        self.players[self.turn].attempts += 1;
+        if outcome == Outcome::Correct {
+            self.winner = Some(self.turn);
+        } else {
+            self.next_turn();
+        }
+        outcome
+    }
+
+    /// Answers a clue for the current player, charging them an attempt.
+    pub fn buy_clue(&mut self, clue: Clue) -> Option<ClueAnswer> {
+        if self.is_over() {
+            return None;
+        }
+
+        let answer = self.game.buy_clue(clue)?;
+        self.players[self.turn].attempts += 1;
+        self.next_turn();
+        Some(answer)
+    }
+
+    fn can_guess(&self, player: &Player) -> bool {
+        self.max_attempts.is_none_or(|max| player.attempts < max)
+    }
+
+    fn next_turn(&mut self) {
+        for step in 1..=self.players.len() {
+            let next = (self.turn + step) % self.players.len();
+            if self.can_guess(&self.players[next]) {
+                self.turn = next;
+                return;
+            }
+        }
+    }
+}
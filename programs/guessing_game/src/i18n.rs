@@ -0,0 +1,188 @@
+//! Message catalogs for everything the game says to the player.
+//!
+//! Each language is a `locales/<code>.txt` file of `key = message` lines
+//! compiled into the binary. Messages may contain `{name}` placeholders that
+//! are filled in by [`Messages::format`].
+
+use serde::{Deserialize, Serialize};
+
+use std::collections::BTreeMap;
+use std::env;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Locale {
+    #[default]
+    En,
+    De,
+}
+
+impl Locale {
+    pub const ALL: [Locale; 2] = [Locale::En, Locale::De];
+
+    /// The locale named by `LC_ALL`, `LC_MESSAGES` or `LANG`, in that order
+    /// as POSIX does, falling back to English.
+    pub fn from_env() -> Locale {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|name| env::var(name).ok())
+            .find(|value| !value.is_empty())
+            .and_then(|value| value.parse().ok())
+            .unwrap_or_default()
+    }
+
+    fn source(self) -> &'static str {
+        match self {
+            Locale::En => include_str!("../locales/en.txt"),
+            Locale::De => include_str!("../locales/de.txt"),
+        }
+    }
+
+    pub fn catalog(self) -> &'static Catalog {
+        static CATALOGS: [OnceLock<Catalog>; Locale::ALL.len()] =
+            [OnceLock::new(), OnceLock::new()];
+
+        CATALOGS[self as usize].get_or_init(|| {
+            Catalog::parse(self.source())
+                .unwrap_or_else(|err| panic!("bad message catalog for {self}: {err}"))
+        })
+    }
+}
+
+impl fmt::Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let code = match self {
+            Locale::En => "en",
+            Locale::De => "de",
+        };
+        f.write_str(code)
+    }
+}
+
+impl FromStr for Locale {
+    type Err = String;
+
+    /// Accepts a language code such as `de`, or a POSIX locale such as
+    /// `de_DE.UTF-8`. `C` and `POSIX` mean English.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let language = s.split(['_', '-', '.', '@']).next().unwrap_or_default();
+        match language.to_ascii_lowercase().as_str() {
+            "en" | "c" | "posix" => Ok(Locale::En),
+            "de" => Ok(Locale::De),
+            _ => Err(format!("unsupported language '{s}' (expected en or de)")),
+        }
+    }
+}
+
+/// One language's messages by key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Catalog {
+    messages: BTreeMap<&'static str, &'static str>,
+}
+
+impl Catalog {
+    /// Reads `key = message` lines, skipping blanks and `#` comments.
+    pub fn parse(source: &'static str) -> Result<Catalog, String> {
+        let mut messages = BTreeMap::new();
+        for (index, line) in source.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let Some((key, message)) = line.split_once('=') else {
+                return Err(format!("line {}: expected 'key = message'", index + 1));
+            };
+            if messages.insert(key.trim(), message.trim()).is_some() {
+                return Err(format!(
+                    "line {}: duplicate key '{}'",
+                    index + 1,
+                    key.trim()
+                ));
+            }
+        }
+        Ok(Catalog { messages })
+    }
+
+    pub fn get(&self, key: &str) -> Option<&'static str> {
+        self.messages.get(key).copied()
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.messages.keys().copied()
+    }
+}
+
+/// Looks messages up in one locale, falling back to English for anything
+/// the locale's catalog is missing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Messages {
+    locale: Locale,
+}
+
+impl Messages {
+    pub fn new(locale: Locale) -> Self {
+        Self { locale }
+    }
+
+    pub fn locale(&self) -> Locale {
+        self.locale
+    }
+
+    /// The message for `key`. A key missing from every catalog is a bug:
+    /// debug builds panic and release builds show `???`.
+    pub fn text(&self, key: &str) -> &'static str {
+        let message = self
+            .locale
+            .catalog()
+            .get(key)
+            .or_else(|| Locale::En.catalog().get(key));
+        if cfg!(debug_assertions) && message.is_none() {
+            panic!("no message for key '{key}'");
+        }
+        message.unwrap_or("???")
+    }
+
+    /// The message for `key` with each `{name}` replaced by its value.
+    pub fn format(&self, key: &str, args: &[(&str, &dyn fmt::Display)]) -> String {
+        let mut message = String::new();
+        let mut rest = self.text(key);
+        while let Some(open) = rest.find('{') {
+            message.push_str(&rest[..open]);
+            rest = &rest[open..];
+
+            let value = rest
+                .find('}')
+                .and_then(|close| args.iter().find(|(name, _)| *name == &rest[1..close]));
+            match value {
+                Some((name, value)) => {
+                    message.push_str(&value.to_string());
+                    rest = &rest[name.len() + 2..];
+                }
+                None => {
+                    message.push('{');
+                    rest = &rest[1..];
+                }
+            }
+        }
+        message.push_str(rest);
+        message
+    }
+}
+
+impl Default for Messages {
+    fn default() -> Self {
+        Self::new(Locale::default())
+    }
+}
+
+/// The `{name}` placeholders in `message`, in order of appearance.
+pub fn placeholders(message: &str) -> Vec<&str> {
+    message
+        .split('{')
+        .skip(1)
+        .filter_map(|rest| rest.split_once('}').map(|(name, _)| name))
+        .collect()
+}
@@ -0,0 +1,553 @@
+pub mod console;
+pub mod daily;
+pub mod distribution;
+pub mod hints;
+pub mod hotseat;
+pub mod i18n;
+pub mod multi;
+pub mod number;
+pub mod protocol;
+pub mod reverse;
+pub mod save;
+pub mod scores;
+pub mod server;
+pub mod simulate;
+pub mod solver;
+pub mod timer;
+pub mod transcript;
+#[cfg(feature = "tui")]
+pub mod tui;
+pub mod ulam;
+pub mod words;
+
+use rand::{Rng, SeedableRng, random_range};
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Bound, RangeInclusive};
+use std::str::FromStr;
+
+use hints::{Clue, ClueAnswer, Hint, HintMode};
+use ulam::Liar;
+
+/// Picks the secret number for a new game.
+pub trait SecretSource {
+    fn pick(&mut self, range: &RangeInclusive<u64>) -> u64;
+
+    /// Seed that reproduces the most recently picked secret, if there is one.
+    fn seed(&self) -> Option<u64> {
+        None
+    }
+}
+
+impl<S: SecretSource + ?Sized> SecretSource for &mut S {
+    fn pick(&mut self, range: &RangeInclusive<u64>) -> u64 {
+        (**self).pick(range)
+    }
+
+    fn seed(&self) -> Option<u64> {
+        (**self).seed()
+    }
+}
+
+/// Uniformly random secret from the thread RNG.
+pub struct RandomSecret;
+
+impl SecretSource for RandomSecret {
+    fn pick(&mut self, range: &RangeInclusive<u64>) -> u64 {
+        random_range(range.clone())
+    }
+}
+
+/// Deterministic secrets from a ChaCha8 stream, identical on every platform.
+///
+/// The first secret is drawn from `seed` itself and every later one from a
+/// seed derived from it, so each game reports a seed that replays it alone.
+pub struct SeededSecret {
+    next_seed: u64,
+    last_seed: Option<u64>,
+    seeds: ChaCha8Rng,
+}
+
+impl SeededSecret {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            next_seed: seed,
+            last_seed: None,
+            seeds: ChaCha8Rng::seed_from_u64(seed),
+        }
+    }
+
+    /// Seeded from the thread RNG, for games that should still be replayable.
+    pub fn from_entropy() -> Self {
+        Self::new(rand::random())
+    }
+}
+
+impl SecretSource for SeededSecret {
+    fn pick(&mut self, range: &RangeInclusive<u64>) -> u64 {
+        let seed = self.next_seed;
+        self.next_seed = self.seeds.random();
+        self.last_seed = Some(seed);
+
+        ChaCha8Rng::seed_from_u64(seed).random_range(range.clone())
+    }
+
+    fn seed(&self) -> Option<u64> {
+        self.last_seed
+    }
+}
+
+/// Always the same secret. Handy for tests and bots.
+pub struct FixedSecret(pub u64);
+
+impl SecretSource for FixedSecret {
+    fn pick(&mut self, _range: &RangeInclusive<u64>) -> u64 {
+        self.0
+    }
+}
+
+/// Named presets bundling a range with an attempt cap.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Difficulty {
+    /// 1 to 20 in 10 attempts.
+    Easy,
+    /// 1 to 100 in 8 attempts.
+    Normal,
+    /// 1 to 1000 in 10 attempts.
+    Hard,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 3] = [Difficulty::Easy, Difficulty::Normal, Difficulty::Hard];
+
+    pub fn range(self) -> RangeInclusive<u64> {
+        match self {
+            Difficulty::Easy => 1..=20,
+            Difficulty::Normal => 1..=100,
+            Difficulty::Hard => 1..=1000,
+        }
+    }
+
+    pub fn max_attempts(self) -> u32 {
+        match self {
+            Difficulty::Easy => 10,
+            Difficulty::Normal => 8,
+            Difficulty::Hard => 10,
+        }
+    }
+}
+
+impl fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Difficulty::Easy => "easy",
+            Difficulty::Normal => "normal",
+            Difficulty::Hard => "hard",
+        };
+        f.write_str(name)
+    }
+}
+
+impl FromStr for Difficulty {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "easy" => Ok(Difficulty::Easy),
+            "normal" => Ok(Difficulty::Normal),
+            "hard" => Ok(Difficulty::Hard),
+            _ => Err(format!(
+                "unknown difficulty '{s}' (expected easy, normal or hard)"
+            )),
+        }
+    }
+}
+
+/// What the secret is: a number, a word from the bundled list or a date.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SecretKind {
+    #[default]
+    Number,
+    Word,
+    Date,
+}
+
+impl SecretKind {
+    pub const ALL: [SecretKind; 3] = [SecretKind::Number, SecretKind::Word, SecretKind::Date];
+}
+
+impl fmt::Display for SecretKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            SecretKind::Number => "number",
+            SecretKind::Word => "word",
+            SecretKind::Date => "date",
+        };
+        f.write_str(name)
+    }
+}
+
+impl FromStr for SecretKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "number" => Ok(SecretKind::Number),
+            "word" => Ok(SecretKind::Word),
+            "date" => Ok(SecretKind::Date),
+            _ => Err(format!(
+                "unknown kind of secret '{s}' (expected number, word or date)"
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    TooSmall,
+    TooBig,
+    Correct,
+    OutOfRange,
+    /// Earlier answers already rule this guess out.
+    RuledOut,
+    /// The game was already won or lost; nothing was counted.
+    GameOver,
+}
+
+/// One game over secrets of type `T`, which only needs a total order.
+/// Numbers get distance hints and clues on top.
+#[derive(Debug)]
+pub struct Game<T = u64> {
+    range: RangeInclusive<T>,
+    secret: T,
+    seed: Option<u64>,
+    attempts: u32,
+    max_attempts: Option<u32>,
+    won: bool,
+    /// Lost to a time limit rather than by running out of attempts.
+    expired: bool,
+    free_repeats: bool,
+    hint_modes: Vec<HintMode>,
+    guesses: Vec<T>,
+    /// The largest guess that was too small.
+    above: Option<T>,
+    /// The smallest guess that was too big.
+    below: Option<T>,
+    /// Set in Ulam mode, where some answers may be lies.
+    liar: Option<Liar>,
+    /// Positions in `guesses` whose answer was a lie.
+    lies: Vec<usize>,
+}
+
+impl Game {
+    /// Panics if `range` is empty.
+    pub fn new(range: RangeInclusive<u64>, mut secret_source: impl SecretSource) -> Self {
+        assert!(!range.is_empty(), "the guessing range must not be empty");
+
+        let secret = secret_source.pick(&range);
+        Self::with_secret(range, secret, secret_source.seed())
+    }
+
+    /// Extra hints about the latest guess, one per enabled hint mode that has
+    /// something to say.
+    pub fn hints(&self) -> Vec<Hint> {
+        let Some((&guess, earlier)) = self.guesses.split_last() else {
+            return Vec::new();
+        };
+        if guess == self.secret {
+            return Vec::new();
+        }
+
+        let mut hints = Vec::new();
+        for mode in &self.hint_modes {
+            match mode {
+                HintMode::WarmerColder => {
+                    if let Some(&previous) = earlier.last() {
+                        hints.push(Hint::warmer_colder(self.secret, guess, previous));
+                    }
+                }
+                HintMode::Distance => hints.push(Hint::distance(self.secret, guess)),
+                HintMode::Interval => {
+                    let interval = self.interval();
+                    hints.push(Hint::Interval(*interval.start(), *interval.end()));
+                }
+                HintMode::Clues => {}
+            }
+        }
+        hints
+    }
+
+    /// Answers `clue` in exchange for one attempt. Returns `None` if clues
+    /// aren't enabled for this game or it is already over.
+    pub fn buy_clue(&mut self, clue: Clue) -> Option<ClueAnswer> {
+        if !self.hint_modes.contains(&HintMode::Clues) || self.is_over() {
+            return None;
+        }
+
+        self.attempts += 1;
+        Some(clue.answer(self.secret))
+    }
+
+    /// The numbers the secret can still be, judging by the guesses so far.
+    pub fn interval(&self) -> RangeInclusive<u64> {
+        let low = match self.bounds().0 {
+            Bound::Excluded(&above) => above + 1,
+            Bound::Included(&low) => low,
+            Bound::Unbounded => unreachable!("games are always bounded"),
+        };
+        let high = match self.bounds().1 {
+            Bound::Excluded(&below) => below - 1,
+            Bound::Included(&high) => high,
+            Bound::Unbounded => unreachable!("games are always bounded"),
+        };
+        low..=high
+    }
+}
+
+impl<T: Ord + Clone> Game<T> {
+    /// A game whose secret is drawn from `candidates`, e.g. a word list. The
+    /// source picks a position in the sorted list, so seeds replay these
+    /// games just like numeric ones.
+    ///
+    /// Panics if `candidates` is empty.
+    pub fn from_candidates(
+        candidates: impl IntoIterator<Item = T>,
+        mut secret_source: impl SecretSource,
+    ) -> Self {
+        let mut candidates: Vec<T> = candidates.into_iter().collect();
+        candidates.sort_unstable();
+        candidates.dedup();
+        assert!(!candidates.is_empty(), "there must be something to guess");
+
+        let last = candidates.len() as u64 - 1;
+        let index = secret_source.pick(&(0..=last)) as usize;
+        let range = candidates[0].clone()..=candidates[last as usize].clone();
+        Self::with_secret(range, candidates.swap_remove(index), secret_source.seed())
+    }
+
+    /// A game over the `len` values `nth(0)` to `nth(len - 1)`, which must
+    /// be in ascending order, e.g. consecutive days. Like
+    /// [`Game::from_candidates`] but without listing every value, so huge
+    /// spans cost nothing.
+    ///
+    /// Panics if `len` is zero.
+    pub fn from_sequence(
+        len: u64,
+        nth: impl Fn(u64) -> T,
+        mut secret_source: impl SecretSource,
+    ) -> Self {
+        assert!(len > 0, "there must be something to guess");
+
+        let index = secret_source.pick(&(0..=len - 1));
+        let range = nth(0)..=nth(len - 1);
+        Self::with_secret(range, nth(index), secret_source.seed())
+    }
+
+    fn with_secret(range: RangeInclusive<T>, secret: T, seed: Option<u64>) -> Self {
+        Self {
+            range,
+            secret,
+            seed,
+            attempts: 0,
+            max_attempts: None,
+            won: false,
+            expired: false,
+            free_repeats: false,
+            hint_modes: Vec::new(),
+            guesses: Vec::new(),
+            above: None,
+            below: None,
+            liar: None,
+            lies: Vec::new(),
+        }
+    }
+
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = Some(max_attempts);
+        self
+    }
+
+    pub fn with_hints(mut self, modes: impl IntoIterator<Item = HintMode>) -> Self {
+        for mode in modes {
+            if !self.hint_modes.contains(&mode) {
+                self.hint_modes.push(mode);
+            }
+        }
+        self
+    }
+
+    /// Don't charge an attempt for guesses that earlier answers already rule
+    /// out.
+    pub fn with_free_repeats(mut self, free_repeats: bool) -> Self {
+        self.free_repeats = free_repeats;
+        self
+    }
+
+    /// Lets `liar` turn some "too small" answers into "too big" and back,
+    /// as in Ulam's searching game. "Correct" is always the truth. As no
+    /// answer can be trusted, nothing is ever `RuledOut`.
+    pub fn with_lies(mut self, liar: Liar) -> Self {
+        self.liar = Some(liar);
+        self
+    }
+
+    /// Compares a guess against the secret. Guesses outside the range are
+    /// rejected and don't count as an attempt.
+    ///
+    /// A guess the earlier answers already exclude, such as a repeat, is
+    /// flagged as `RuledOut` and costs an attempt unless free repeats are on.
+    pub fn guess(&mut self, guess: T) -> Outcome {
+        if self.is_over() {
+            return Outcome::GameOver;
+        }
+        if !self.range.contains(&guess) {
+            return Outcome::OutOfRange;
+        }
+        let too_small = self.above.as_ref().is_some_and(|above| guess <= *above);
+        let too_big = self.below.as_ref().is_some_and(|below| guess >= *below);
+        if too_small || too_big {
+            if !self.free_repeats {
+                self.attempts += 1;
+            }
+            return Outcome::RuledOut;
+        }
+
+        self.attempts += 1;
+        self.guesses.push(guess.clone());
+
+        let truth = guess.cmp(&self.secret);
+        let told = self.lies.len() as u32;
+        let lie =
+            truth != Ordering::Equal && self.liar.as_mut().is_some_and(|liar| liar.lies(told));
+        let answer = if lie {
+            self.lies.push(self.guesses.len() - 1);
+            truth.reverse()
+        } else {
+            truth
+        };
+
+        match answer {
+            Ordering::Less if self.liar.is_none() => {
+                self.above = Some(guess);
+                Outcome::TooSmall
+            }
+            Ordering::Greater if self.liar.is_none() => {
+                self.below = Some(guess);
+                Outcome::TooBig
+            }
+            Ordering::Less => Outcome::TooSmall,
+            Ordering::Greater => Outcome::TooBig,
+            Ordering::Equal => {
+                self.won = true;
+                Outcome::Correct
+            }
+        }
+    }
+
+    /// Charges an attempt without a guess, e.g. when the player ran out of
+    /// time for it. Does nothing once the game is over.
+    pub fn forfeit(&mut self) {
+        if !self.is_over() {
+            self.attempts += 1;
+        }
+    }
+
+    /// Ends the game as lost, e.g. when its time limit runs out.
+    pub fn expire(&mut self) {
+        if !self.won {
+            self.expired = true;
+        }
+    }
+
+    pub fn range(&self) -> &RangeInclusive<T> {
+        &self.range
+    }
+
+    pub fn secret(&self) -> T {
+        self.secret.clone()
+    }
+
+    /// Seed to pass to `SeededSecret::new` to get this game's secret again.
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+
+    pub fn attempts(&self) -> u32 {
+        self.attempts
+    }
+
+    pub fn max_attempts(&self) -> Option<u32> {
+        self.max_attempts
+    }
+
+    /// Attempts left before the game is lost, if there is a limit.
+    pub fn remaining_attempts(&self) -> Option<u32> {
+        self.max_attempts
+            .map(|max| max.saturating_sub(self.attempts))
+    }
+
+    pub fn free_repeats(&self) -> bool {
+        self.free_repeats
+    }
+
+    pub fn hint_modes(&self) -> &[HintMode] {
+        &self.hint_modes
+    }
+
+    /// Lies the game may tell, if it plays Ulam's game.
+    pub fn max_lies(&self) -> Option<u32> {
+        self.liar.as_ref().map(Liar::max_lies)
+    }
+
+    /// Positions in `guesses` whose answer was a lie.
+    pub fn lies(&self) -> &[usize] {
+        &self.lies
+    }
+
+    /// Every counted guess so far, oldest first.
+    pub fn guesses(&self) -> &[T] {
+        &self.guesses
+    }
+
+    /// Where the secret can still be, judging by the guesses so far: past
+    /// the closest guesses on either side, or within the range if there are
+    /// none yet. Both ends are the secret once it's found.
+    pub fn bounds(&self) -> (Bound<&T>, Bound<&T>) {
+        if self.won {
+            return (Bound::Included(&self.secret), Bound::Included(&self.secret));
+        }
+        let low = self
+            .above
+            .as_ref()
+            .map_or(Bound::Included(self.range.start()), Bound::Excluded);
+        let high = self
+            .below
+            .as_ref()
+            .map_or(Bound::Included(self.range.end()), Bound::Excluded);
+        (low, high)
+    }
+
+    pub fn is_won(&self) -> bool {
+        self.won
+    }
+
+    /// True once every allowed attempt has been used, or the time has run
+    /// out, without finding the secret.
+    pub fn is_lost(&self) -> bool {
+        !self.won && (self.expired || self.max_attempts.is_some_and(|max| self.attempts >= max))
+    }
+
+    /// True if the game was lost to a time limit.
+    pub fn is_expired(&self) -> bool {
+        self.expired
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.is_won() || self.is_lost()
+    }
+}
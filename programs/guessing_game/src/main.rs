@@ -1,37 +1,1923 @@
-use rand::random_range;
+use clap::builder::FalseyValueParser;
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use guessing_game::console::{self, Guess, Input, is_quit_command, is_save_command};
+use guessing_game::daily::{self, DailySecret, Date};
+use guessing_game::distribution::{DistributedSecret, Distribution};
+use guessing_game::hints::HintMode;
+use guessing_game::hotseat::HotSeat;
+use guessing_game::i18n::{Locale, Messages};
+use guessing_game::multi::{self, MultiGame, MultiOutcome, MultiSolver};
+use guessing_game::number;
+use guessing_game::protocol;
+use guessing_game::reverse::{Reply, ReverseGame};
+use guessing_game::save::{self, SavedGame};
+use guessing_game::scores::{self, Score, ScoreFile};
+use guessing_game::server::{Server, ServerConfig};
+use guessing_game::simulate::{self, Simulation};
+use guessing_game::solver::{self, Strategy};
+use guessing_game::timer::{self, Countdown, Expiry, SystemClock, TimeLimits};
+use guessing_game::transcript::{Entry, Recorder, Setup, Transcript};
+use guessing_game::ulam::{self, Liar, UlamSolver};
+use guessing_game::{Difficulty, FixedSecret, Game, Outcome, SecretKind, SeededSecret, words};
 
-use std::cmp::Ordering;
-use std::io;
+use std::env;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, IsTerminal, Write};
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+use std::process::{self, ExitCode};
+use std::sync::mpsc::{Receiver, RecvTimeoutError};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
 
-fn main() {
-    let secret = random_range(1..=100);
+#[derive(Parser, Debug)]
+#[command(
+    about = "Guess the secret number",
+    args_conflicts_with_subcommands = true
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
 
-    println!("Secret number is: {}", secret);
+    /// Language for messages: en or de (default: from LC_ALL, LC_MESSAGES or LANG)
+    #[arg(long, global = true, value_name = "LANG")]
+    lang: Option<Locale>,
 
-    println!("Welcome to the Guessing Game!");
-    println!("Input a guess from 1 to 100: ");
+    #[command(flatten)]
+    play: PlayArgs,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Run many bot games per strategy and report attempt statistics
+    Simulate(SimulateArgs),
+
+    /// List the best recorded games for each difficulty
+    Scores(ScoresArgs),
+
+    /// Host games for TCP clients speaking the line protocol
+    Serve(ServeArgs),
+
+    /// Think of a number and let the computer guess it
+    Reverse(ReverseArgs),
+
+    /// Re-run a game saved with --record and check every response matches
+    Replay(ReplayArgs),
+}
+
+#[derive(Args, Debug)]
+struct RulesArgs {
+    /// Preset range and attempt limit: easy, normal or hard
+    #[arg(short, long)]
+    difficulty: Option<Difficulty>,
+
+    /// Lowest possible secret (overrides the preset)
+    #[arg(long)]
+    min: Option<u64>,
+
+    /// Highest possible secret (overrides the preset)
+    #[arg(long)]
+    max: Option<u64>,
+
+    /// Guesses allowed before losing (overrides the preset)
+    #[arg(long, value_parser = clap::value_parser!(u32).range(1..))]
+    max_attempts: Option<u32>,
+}
+
+impl RulesArgs {
+    fn range(&self) -> Result<RangeInclusive<u64>, String> {
+        let preset = self.difficulty.map(Difficulty::range).unwrap_or(1..=100);
+        let min = self.min.unwrap_or(*preset.start());
+        let max = self.max.unwrap_or(*preset.end());
+        if min > max {
+            return Err(format!(
+                "--min ({min}) must not be greater than --max ({max})"
+            ));
+        }
+        Ok(min..=max)
+    }
+
+    fn max_attempts(&self) -> Option<u32> {
+        self.max_attempts
+            .or(self.difficulty.map(Difficulty::max_attempts))
+    }
+}
+
+#[derive(Args, Debug)]
+struct DistributionArgs {
+    /// How the secret is drawn: uniform (default), normal or exponential
+    #[arg(long, value_name = "NAME")]
+    distribution: Option<Distribution>,
+
+    /// Draw the secret using a file of '<number> <weight>' lines instead
+    #[arg(long, value_name = "FILE", conflicts_with = "distribution")]
+    weights: Option<PathBuf>,
+}
+
+impl DistributionArgs {
+    /// The chosen distribution, checked against `range`.
+    fn load(&self, range: &RangeInclusive<u64>) -> io::Result<Distribution> {
+        let distribution = match &self.weights {
+            Some(path) => {
+                let file = File::open(path).map_err(|err| with_path(path, err))?;
+                Distribution::load(BufReader::new(file)).map_err(|err| with_path(path, err))?
+            }
+            None => self.distribution.clone().unwrap_or_default(),
+        };
+        distribution.check(range).map_err(invalid_input)?;
+        Ok(distribution)
+    }
+}
+
+#[derive(Args, Debug)]
+struct PlayArgs {
+    #[command(flatten)]
+    rules: RulesArgs,
+
+    #[command(flatten)]
+    secrets: DistributionArgs,
+
+    /// What to guess: number, word (from the bundled list) or date
+    #[arg(long, default_value_t)]
+    kind: SecretKind,
+
+    /// Earliest date a date secret can be (default: January 1st this year)
+    #[arg(long, value_name = "DATE")]
+    from: Option<Date>,
+
+    /// Latest date a date secret can be (default: December 31st this year)
+    #[arg(long, value_name = "DATE")]
+    to: Option<Date>,
+
+    /// Show the secret before the first guess
+    #[arg(long, env = "GUESS_DEBUG", value_parser = FalseyValueParser::new())]
+    reveal: bool,
+
+    /// Seed for the secret, to replay a game exactly
+    #[arg(long)]
+    seed: Option<u64>,
+
+    /// Daily challenge: the same secret for everyone on DATE (default: today, UTC)
+    #[arg(
+        long,
+        value_name = "DATE",
+        value_parser = parse_day,
+        num_args = 0..=1,
+        default_missing_value = "today",
+        conflicts_with_all = ["seed", "players", "distribution", "weights"]
+    )]
+    daily: Option<Date>,
+
+    /// Let a bot play instead: binary (default), random, linear or entropy
+    #[arg(
+        long,
+        conflicts_with = "players",
+        value_name = "STRATEGY",
+        num_args = 0..=1,
+        default_missing_value = "binary"
+    )]
+    auto: Option<Strategy>,
+
+    /// Extra hints, comma separated: warmer, distance, interval, clues
+    /// (words and dates get warmer and interval)
+    #[arg(long, value_delimiter = ',')]
+    hints: Vec<HintMode>,
+
+    /// Hard mode: up to N of the "too small"/"too big" answers may be lies
+    #[arg(
+        long,
+        value_name = "N",
+        value_parser = clap::value_parser!(u32).range(..=ulam::MAX_LIES as i64),
+        conflicts_with_all = ["hints", "players", "record"]
+    )]
+    lies: Option<u32>,
+
+    /// Hide N secrets at once; each guess counts those below, at and above it.
+    /// Difficulty presets allow their attempts once per secret
+    #[arg(
+        long = "secrets",
+        value_name = "N",
+        value_parser = clap::value_parser!(u32).range(1..=multi::MAX_SECRETS as i64),
+        conflicts_with_all = [
+            "daily", "distribution", "weights", "hints", "lies", "free_repeats", "players",
+            "record", "json", "guess_time", "game_time", "resume"
+        ]
+    )]
+    secret_count: Option<u32>,
+
+    /// Don't charge an attempt for repeating a guess already ruled out
+    #[arg(long)]
+    free_repeats: bool,
+
+    /// Hot-seat mode: two or more comma separated names taking turns
+    #[arg(
+        long,
+        value_delimiter = ',',
+        conflicts_with_all = ["distribution", "weights"]
+    )]
+    players: Vec<String>,
+
+    /// Hot-seat player who picks the secret instead of guessing it
+    #[arg(long, requires = "players")]
+    chooser: Option<String>,
+
+    /// Play full screen instead of line by line
+    #[cfg(feature = "tui")]
+    #[arg(
+        long,
+        conflicts_with_all = [
+            "auto", "players", "record", "guess_time", "game_time", "json", "secret_count",
+            "resume"
+        ]
+    )]
+    tui: bool,
+
+    /// Save every input and response to FILE, for the replay command
+    #[arg(
+        long,
+        value_name = "FILE",
+        conflicts_with_all = ["auto", "players", "distribution", "weights"]
+    )]
+    record: Option<PathBuf>,
+
+    /// Play over stdin and stdout in JSON lines, for bots (see the protocol docs)
+    #[arg(
+        long,
+        conflicts_with_all = ["auto", "players", "hints", "record", "guess_time", "game_time"]
+    )]
+    json: bool,
+
+    /// Seconds allowed per guess; running out costs an attempt
+    #[arg(
+        long,
+        value_name = "SECS",
+        value_parser = parse_seconds,
+        conflicts_with_all = ["auto", "players", "record"]
+    )]
+    guess_time: Option<Duration>,
+
+    /// Seconds allowed for the whole game; running out loses it
+    #[arg(
+        long,
+        value_name = "SECS",
+        value_parser = parse_seconds,
+        conflicts_with_all = ["auto", "players", "record"]
+    )]
+    game_time: Option<Duration>,
+
+    /// Continue the game last saved with 'save' or interrupted with Ctrl-C
+    #[arg(
+        long,
+        conflicts_with_all = [
+            "difficulty", "min", "max", "max_attempts", "kind", "seed", "daily", "auto",
+            "distribution", "weights",
+            "hints", "lies", "free_repeats", "players", "record", "json", "guess_time",
+            "game_time"
+        ]
+    )]
+    resume: bool,
+
+    /// Name to record in the high-score table (default: $USER)
+    #[arg(long, env = "GUESS_PLAYER")]
+    name: Option<String>,
+}
+
+impl PlayArgs {
+    fn secret_source(&self) -> SeededSecret {
+        match self.seed {
+            Some(seed) => SeededSecret::new(seed),
+            None => SeededSecret::from_entropy(),
+        }
+    }
+
+    fn date_range(&self) -> Result<RangeInclusive<Date>, String> {
+        let year = Date::today().year();
+        let from = self.from.unwrap_or_else(|| Date::new(year, 1, 1));
+        let to = self.to.unwrap_or_else(|| Date::new(year, 12, 31));
+        if from > to {
+            return Err(format!("--from ({from}) must not be after --to ({to})"));
+        }
+        Ok(from..=to)
+    }
+
+    /// The first option given that only makes sense when guessing numbers.
+    fn numbers_only(&self) -> Option<&'static str> {
+        #[cfg(feature = "tui")]
+        let tui = self.tui;
+        #[cfg(not(feature = "tui"))]
+        let tui = false;
+
+        [
+            ("--min", self.rules.min.is_some()),
+            ("--max", self.rules.max.is_some()),
+            ("--distribution", self.secrets.distribution.is_some()),
+            ("--weights", self.secrets.weights.is_some()),
+            ("--daily", self.daily.is_some()),
+            ("--auto", self.auto.is_some()),
+            ("--hints distance", self.hints.contains(&HintMode::Distance)),
+            ("--hints clues", self.hints.contains(&HintMode::Clues)),
+            ("--lies", self.lies.is_some()),
+            ("--secrets", self.secret_count.is_some()),
+            ("--players", !self.players.is_empty()),
+            ("--tui", tui),
+            ("--record", self.record.is_some()),
+            ("--json", self.json),
+        ]
+        .into_iter()
+        .find_map(|(flag, given)| given.then_some(flag))
+    }
+
+    /// `None` unless at least one time limit is set.
+    fn time_limits(&self) -> Option<TimeLimits> {
+        let limits = TimeLimits {
+            per_guess: self.guess_time,
+            per_game: self.game_time,
+        };
+        (limits != TimeLimits::default()).then_some(limits)
+    }
+}
+
+fn parse_day(s: &str) -> Result<Date, String> {
+    match s {
+        "today" => Ok(Date::today()),
+        _ => s.parse(),
+    }
+}
+
+fn parse_seconds(s: &str) -> Result<Duration, String> {
+    let seconds: f64 = s
+        .parse()
+        .map_err(|_| format!("'{s}' is not a number of seconds"))?;
+    match Duration::try_from_secs_f64(seconds) {
+        Ok(duration) if !duration.is_zero() => Ok(duration),
+        _ => Err(format!("'{s}' must be a positive number of seconds")),
+    }
+}
+
+#[derive(Args, Debug)]
+struct SimulateArgs {
+    #[command(flatten)]
+    rules: RulesArgs,
+
+    #[command(flatten)]
+    secrets: DistributionArgs,
+
+    /// Games to play per strategy
+    #[arg(short = 'n', long, default_value_t = 10_000)]
+    games: u64,
+
+    /// Seed for the secret sequence shared by every strategy
+    #[arg(long)]
+    seed: Option<u64>,
+
+    /// Strategies to compare, comma separated (default: all)
+    #[arg(long = "strategy", value_delimiter = ',')]
+    strategies: Vec<Strategy>,
+
+    /// Output format
+    #[arg(long, value_enum, default_value_t = Format::Table)]
+    format: Format,
+}
+
+#[derive(Args, Debug)]
+struct ScoresArgs {
+    /// Only show this difficulty
+    #[arg(short, long)]
+    difficulty: Option<Difficulty>,
+
+    /// Results to show per difficulty
+    #[arg(short = 'n', long, default_value_t = 10)]
+    limit: usize,
+
+    /// Rank by wall-clock time instead of attempts
+    #[arg(long)]
+    speedrun: bool,
+}
+
+#[derive(Args, Debug)]
+struct ServeArgs {
+    #[command(flatten)]
+    rules: RulesArgs,
+
+    /// Port to listen on
+    #[arg(short, long, default_value_t = 4242)]
+    port: u16,
+
+    /// Address to listen on
+    #[arg(long, default_value = "127.0.0.1")]
+    host: String,
+
+    /// Every client races to find one shared secret
+    #[arg(long)]
+    race: bool,
+
+    /// Seed for the sequence of secrets handed out
+    #[arg(long)]
+    seed: Option<u64>,
+
+    /// Disconnect clients that send nothing for SECS seconds
+    #[arg(long, value_name = "SECS", value_parser = parse_seconds, default_value = "300")]
+    idle_timeout: Duration,
+
+    /// Most clients served at once; anyone beyond that is turned away
+    #[arg(long, default_value_t = 64, value_parser = clap::value_parser!(u16).range(1..))]
+    max_clients: u16,
+}
+
+#[derive(Args, Debug)]
+struct ReverseArgs {
+    #[command(flatten)]
+    rules: RulesArgs,
+}
+
+#[derive(Args, Debug)]
+struct ReplayArgs {
+    /// Transcript written by --record
+    file: PathBuf,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy)]
+enum Format {
+    Table,
+    Csv,
+    Json,
+}
+
+/// Exit code for games given up or put aside, see `GameEnd`.
+const QUIT: u8 = 3;
+
+/// How a game ended, mapped onto the process exit code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum GameEnd {
+    Won,
+    Quit,
+    OutOfAttempts,
+    OutOfTime,
+    /// The player's answers in reverse mode contradicted each other.
+    Cheated,
+    /// The player saved the game to finish it later.
+    Saved,
+}
+
+impl GameEnd {
+    /// `None` while `game` is still going.
+    fn of<T: Ord + Clone>(game: &Game<T>) -> Option<GameEnd> {
+        if game.is_won() {
+            Some(GameEnd::Won)
+        } else if game.is_expired() {
+            Some(GameEnd::OutOfTime)
+        } else if game.is_lost() {
+            Some(GameEnd::OutOfAttempts)
+        } else {
+            None
+        }
+    }
+
+    fn exit_code(self) -> ExitCode {
+        match self {
+            GameEnd::Won => ExitCode::SUCCESS,
+            GameEnd::Quit | GameEnd::Saved => ExitCode::from(QUIT),
+            GameEnd::OutOfAttempts => ExitCode::from(4),
+            GameEnd::Cheated => ExitCode::from(5),
+            GameEnd::OutOfTime => ExitCode::from(6),
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let messages = Messages::new(cli.lang.unwrap_or_else(Locale::from_env));
+
+    let result = match cli.command {
+        Some(Command::Simulate(args)) => simulate(args, &messages),
+        Some(Command::Scores(args)) => show_scores(args, &messages),
+        Some(Command::Serve(args)) => serve(args),
+        Some(Command::Reverse(args)) => reverse(args, &messages),
+        Some(Command::Replay(args)) => replay(args, &messages),
+        None => run_game(cli.play, &messages),
+    };
+
+    match result {
+        Ok(code) => code,
+        // Whoever was reading our output went away, nothing left to tell them.
+        Err(err) if err.kind() == io::ErrorKind::BrokenPipe => GameEnd::Quit.exit_code(),
+        Err(err) if err.kind() == io::ErrorKind::InvalidInput => {
+            eprintln!("error: {err}");
+            ExitCode::from(2)
+        }
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
 
+fn run_game(mut args: PlayArgs, messages: &Messages) -> io::Result<ExitCode> {
+    if args.kind != SecretKind::Number
+        && let Some(flag) = args.numbers_only()
+    {
+        return Err(invalid_input(format!(
+            "{flag} only works with --kind number"
+        )));
+    }
+    #[cfg(feature = "tui")]
+    if args.tui && args.hints.contains(&HintMode::Clues) {
+        return Err(invalid_input(
+            "--hints clues doesn't work with --tui".to_string(),
+        ));
+    }
+    if args.kind != SecretKind::Date && (args.from.is_some() || args.to.is_some()) {
+        return Err(invalid_input(
+            "--from and --to only work with --kind date".to_string(),
+        ));
+    }
+
+    match args.kind {
+        SecretKind::Number => {}
+        SecretKind::Word => {
+            let words = words::list();
+            let game = Game::from_candidates(words.iter().copied(), args.secret_source());
+            return run_plain(game, words.len() as u64, args, messages);
+        }
+        SecretKind::Date => {
+            let dates = args.date_range().map_err(invalid_input)?;
+            let first = dates.start().unix_days();
+            let count = (dates.end().unix_days() - first) as u64 + 1;
+            let game = Game::from_sequence(
+                count,
+                |day| Date::from_unix_days(first + day as i64),
+                args.secret_source(),
+            );
+            return run_plain(game, count, args, messages);
+        }
+    }
+
+    if !args.players.is_empty() {
+        return run_hotseat(args, messages);
+    }
+    if let Some(count) = args.secret_count {
+        return run_multi(count, args, messages);
+    }
+
+    let mut saved = None;
+    let mut distribution = Distribution::Uniform;
+    let mut game = if args.resume {
+        let (game, save) = resume()?;
+        args.rules.difficulty = save.difficulty;
+        saved = Some(save);
+        game
+    } else {
+        let range = args.rules.range().map_err(invalid_input)?;
+        distribution = args.secrets.load(&range)?;
+        let game = match args.daily {
+            Some(date) => Game::new(range, DailySecret::new(date)),
+            None => {
+                let seed = args.seed.unwrap_or_else(rand::random);
+                Game::new(range, DistributedSecret::new(distribution.clone(), seed))
+            }
+        };
+        let mut game = with_rules(game, &args);
+        if let Some(lies) = args.lies {
+            let seed = game.seed().expect("games from the command line are seeded");
+            game = game.with_lies(Liar::new(lies, seed));
+        }
+        game
+    };
+
+    let mut output = io::stdout().lock();
+    if args.json {
+        protocol::play(&mut game, io::stdin().lock(), &mut output, args.reveal)?;
+        return Ok(GameEnd::of(&game).unwrap_or(GameEnd::Quit).exit_code());
+    }
+    if let Some(strategy) = args.auto {
+        let end = auto_play(
+            &mut game,
+            strategy,
+            &distribution,
+            args.reveal,
+            &mut output,
+            messages,
+        )?;
+        write_distribution(&args, &distribution, &mut output, messages)?;
+        return Ok(end.exit_code());
+    }
+
+    #[cfg(feature = "tui")]
+    let tui = args.tui;
+    #[cfg(not(feature = "tui"))]
+    let tui = false;
+
+    // Only the untimed text loop can stop and pick up again where it left
+    // off, and a save doesn't remember the daily date or the distribution.
+    let autosave = if args.time_limits().is_some()
+        || tui
+        || args.daily.is_some()
+        || distribution != Distribution::Uniform
+    {
+        None
+    } else {
+        let saved = saved.or_else(|| SavedGame::of(&game, args.rules.difficulty));
+        save::default_path()
+            .zip(saved)
+            .map(|(path, saved)| Autosave::new(saved, path))
+    };
+    if let Some(autosave) = &autosave {
+        autosave.on_signal(messages)?;
+    }
+    if args.resume {
+        // Only now that the game can be saved again is the old save spent.
+        let path = &autosave.as_ref().expect("resumed games are saved").path;
+        fs::remove_file(path).map_err(|err| with_path(path, err))?;
+        let resumed = messages.format("resumed", &[("attempts", &game.attempts())]);
+        writeln!(output, "{resumed}")?;
+    }
+
+    let mut recorder = match &args.record {
+        Some(path) => {
+            let setup =
+                Setup::of(&game, messages.locale()).expect("seeded games always have a seed");
+            let file = File::create(path).map_err(|err| with_path(path, err))?;
+            Some(Recorder::new(file, &setup)?)
+        }
+        None => None,
+    };
+
+    let started = Instant::now();
+    let autosave = autosave.as_ref();
+    #[cfg(feature = "tui")]
+    let end = if tui {
+        guessing_game::tui::run(&mut game, messages)?;
+        ended(&game, &mut output, messages)?
+    } else {
+        play_text(
+            &mut game,
+            &args,
+            &mut output,
+            recorder.as_mut(),
+            autosave,
+            messages,
+        )?
+    };
+    #[cfg(not(feature = "tui"))]
+    let end = play_text(
+        &mut game,
+        &args,
+        &mut output,
+        recorder.as_mut(),
+        autosave,
+        messages,
+    )?;
+    if end == GameEnd::Saved {
+        return Ok(end.exit_code());
+    }
+    let elapsed = autosave.map_or(Duration::ZERO, |autosave| autosave.before) + started.elapsed();
+    let seconds = format!("{:.1}", elapsed.as_secs_f64());
+    writeln!(
+        output,
+        "{}",
+        messages.format("summary_time", &[("seconds", &seconds)])
+    )?;
+    write_distribution(&args, &distribution, &mut output, messages)?;
+    if let Some(date) = args.daily
+        && game.is_over()
+    {
+        writeln!(output)?;
+        writeln!(output, "{}", daily::share(&game, date, messages))?;
+    }
+
+    save_score(&args, &game, game.range().clone(), end, elapsed, messages);
+    Ok(end.exit_code())
+}
+
+/// Names the distribution a game's secret was drawn from, as its seed alone
+/// won't replay it. Uniform games need nothing more.
+fn write_distribution(
+    args: &PlayArgs,
+    distribution: &Distribution,
+    out: &mut impl Write,
+    messages: &Messages,
+) -> io::Result<()> {
+    let flag = match (&args.secrets.weights, distribution) {
+        (_, Distribution::Uniform) => return Ok(()),
+        (Some(path), _) => format!("--weights {}", path.display()),
+        (None, distribution) => format!("--distribution {distribution}"),
+    };
+    let line = messages.format(
+        "summary_distribution",
+        &[("distribution", distribution), ("flag", &flag)],
+    );
+    writeln!(out, "{line}")
+}
+
+/// Plays a word or date game, which gets the plain text loop and nothing
+/// number-specific. `count` is how many secrets it could have had.
+fn run_plain<T: Guess>(
+    game: Game<T>,
+    count: u64,
+    args: PlayArgs,
+    messages: &Messages,
+) -> io::Result<ExitCode> {
+    let mut game = with_rules(game, &args);
+    let mut output = io::stdout().lock();
+
+    let started = Instant::now();
+    let end = play_text(&mut game, &args, &mut output, None, None, messages)?;
+    let elapsed = started.elapsed();
+    let seconds = format!("{:.1}", elapsed.as_secs_f64());
+    writeln!(
+        output,
+        "{}",
+        messages.format("summary_time", &[("seconds", &seconds)])
+    )?;
+
+    save_score(&args, &game, 1..=count, end, elapsed, messages);
+    Ok(end.exit_code())
+}
+
+/// Loads the saved game. The caller deletes the save once the game can be
+/// saved again, so it is only resumed once.
+fn resume() -> io::Result<(Game, SavedGame)> {
+    let path = save::default_path().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::NotFound,
+            "no data directory to resume from, set XDG_DATA_HOME or HOME",
+        )
+    })?;
+    let file = match File::open(&path) {
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {
+            return Err(invalid_input(
+                "there is no saved game to resume".to_string(),
+            ));
+        }
+        file => file.map_err(|err| with_path(&path, err))?,
+    };
+
+    let saved = SavedGame::load(BufReader::new(file)).map_err(|err| with_path(&path, err))?;
+    let game = saved.game().map_err(|err| with_path(&path, err))?;
+    Ok((game, saved))
+}
+
+/// The game as it would be saved right now, kept up to date after every
+/// line so the `save` command and the signal handler can both write it.
+#[derive(Clone)]
+struct Autosave {
+    saved: Arc<Mutex<SavedGame>>,
+    path: PathBuf,
+    /// Time played before this run.
+    before: Duration,
+    started: Instant,
+}
+
+impl Autosave {
+    fn new(saved: SavedGame, path: PathBuf) -> Self {
+        Self {
+            before: saved.elapsed(),
+            saved: Arc::new(Mutex::new(saved)),
+            path,
+            started: Instant::now(),
+        }
+    }
+
+    fn record(&self, line: &str) {
+        self.lock().record(line);
+    }
+
+    fn save(&self) -> io::Result<()> {
+        let mut saved = self.lock();
+        saved.set_elapsed(self.before + self.started.elapsed());
+        saved
+            .save(&self.path)
+            .map_err(|err| with_path(&self.path, err))
+    }
+
+    /// Saves the game and exits on Ctrl-C, SIGTERM or SIGHUP, so closing
+    /// the terminal doesn't lose it.
+    fn on_signal(&self, messages: &Messages) -> io::Result<()> {
+        let autosave = self.clone();
+        let notice = messages.format("game_saved", &[("path", &self.path.display())]);
+        ctrlc::set_handler(move || {
+            // The game loop holds the lock on stdout.
+            match autosave.save() {
+                Ok(()) => eprintln!("\n{notice}"),
+                Err(err) => eprintln!("\nerror: {err}"),
+            }
+            process::exit(QUIT.into());
+        })
+        .map_err(io::Error::other)
+    }
+
+    fn lock(&self) -> MutexGuard<'_, SavedGame> {
+        // A panic mid-update leaves at worst one line unrecorded.
+        self.saved.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Applies the rules every kind of game shares.
+fn with_rules<T: Ord + Clone>(game: Game<T>, args: &PlayArgs) -> Game<T> {
+    let game = game
+        .with_hints(args.hints.iter().copied())
+        .with_free_repeats(args.free_repeats);
+    match args.rules.max_attempts() {
+        Some(max_attempts) => game.with_max_attempts(max_attempts),
+        None => game,
+    }
+}
+
+/// Adds a finished game to the high-score table. `range` is what the secret
+/// could have been: the numbers themselves, or list positions for words and
+/// dates. Games that showed the secret up front aren't recorded.
+fn save_score<T: Ord + Clone>(
+    args: &PlayArgs,
+    game: &Game<T>,
+    range: RangeInclusive<u64>,
+    end: GameEnd,
+    elapsed: Duration,
+    messages: &Messages,
+) {
+    if args.reveal {
+        return;
+    }
+    let score = Score {
+        player: args.name.clone().unwrap_or_else(default_player),
+        won: end == GameEnd::Won,
+        attempts: game.attempts(),
+        elapsed_ms: elapsed.as_millis() as u64,
+        min: *range.start(),
+        max: *range.end(),
+        kind: args.kind,
+        difficulty: preset_played(args, game, &range),
+        finished_at: scores::now(),
+    };
+    // A missing or read-only data directory shouldn't spoil the game itself.
+    if let Some(path) = scores::default_path()
+        && let Err(err) = ScoreFile::new(&path).record(&score)
+    {
+        let warning = messages.format(
+            "score_save_failed",
+            &[("path", &path.display()), ("error", &err)],
+        );
+        eprintln!("{warning}");
+    }
+}
+
+/// The preset a game was played under, unless `--min`, `--max` or
+/// `--max-attempts` changed what it sets, which makes it a custom game.
+fn preset_played<T: Ord + Clone>(
+    args: &PlayArgs,
+    game: &Game<T>,
+    range: &RangeInclusive<u64>,
+) -> Option<Difficulty> {
+    let difficulty = args.rules.difficulty?;
+    // Words and dates come from their own lists, presets only cap attempts.
+    let same_range = args.kind != SecretKind::Number || *range == difficulty.range();
+    let same_attempts = game.max_attempts() == Some(difficulty.max_attempts());
+    (same_range && same_attempts).then_some(difficulty)
+}
+
+fn run_hotseat(args: PlayArgs, messages: &Messages) -> io::Result<ExitCode> {
+    let range = args.rules.range().map_err(invalid_input)?;
+    if args.players.len() < 2 {
+        return Err(invalid_input(
+            "--players needs at least two names".to_string(),
+        ));
+    }
+
+    let mut guessers = args.players.clone();
+    if let Some(chooser) = &args.chooser {
+        guessers.retain(|name| name != chooser);
+        if guessers.len() == args.players.len() {
+            return Err(invalid_input(format!(
+                "--chooser {chooser} is not one of the --players"
+            )));
+        }
+        if guessers.is_empty() {
+            return Err(invalid_input("nobody is left to guess".to_string()));
+        }
+        if args.reveal {
+            return Err(invalid_input(
+                "--reveal can't show a secret the --chooser hasn't picked yet".to_string(),
+            ));
+        }
+    }
+
+    let mut input = io::stdin().lock();
+    let mut output = io::stdout().lock();
+
+    let game = match &args.chooser {
+        Some(chooser) => match choose_secret(chooser, &range, &mut input, &mut output, messages)? {
+            Some(secret) => Game::new(range, FixedSecret(secret)),
+            None => return Ok(GameEnd::Quit.exit_code()),
+        },
+        None => match args.seed {
+            Some(seed) => Game::new(range, SeededSecret::new(seed)),
+            None => Game::new(range, SeededSecret::from_entropy()),
+        },
+    };
+    let game = game
+        .with_hints(args.hints)
+        .with_free_repeats(args.free_repeats);
+
+    if args.reveal {
+        let secret = messages.format("secret_is", &[("secret", &game.secret())]);
+        writeln!(output, "{secret}")?;
+    }
+
+    let mut hotseat = HotSeat::new(game, guessers, args.rules.max_attempts());
+    let end = play_hotseat(&mut hotseat, &mut input, &mut output, messages)?;
+    Ok(end.exit_code())
+}
+
+/// Asks the chooser for a secret without echoing it. `None` if they quit.
+fn choose_secret(
+    chooser: &str,
+    range: &RangeInclusive<u64>,
+    input: &mut impl BufRead,
+    out: &mut impl Write,
+    messages: &Messages,
+) -> io::Result<Option<u64>> {
     loop {
-        let mut guess = String::new();
-        io::stdin()
-            .read_line(&mut guess)
-            .expect("Failed to read line");
-
-        let guess: u32 = match guess.trim().parse() {
-            Ok(num) => num,
-            Err(_) => {
-                println!("Please input a valid number!");
+        let prompt = messages.format(
+            "chooser_prompt",
+            &[
+                ("name", &chooser),
+                ("min", range.start()),
+                ("max", range.end()),
+            ],
+        );
+        write!(out, "{prompt} ")?;
+        out.flush()?;
+
+        let mut secret = String::new();
+        if read_hidden_line(input, &mut secret)? == 0 {
+            return Ok(None);
+        }
+        writeln!(out)?;
+
+        let secret = secret.trim();
+        if is_quit_command(secret) {
+            return Ok(None);
+        }
+        match number::parse(secret) {
+            Ok(secret) if range.contains(&secret) => return Ok(Some(secret)),
+            Ok(_) => writeln!(out, "{}", messages.text("chooser_out_of_range"))?,
+            Err(err) => writeln!(out, "{}", console::invalid(err, messages))?,
+        }
+    }
+}
+
+/// Reads a line with terminal echo turned off, if stdin is a terminal.
+///
+/// Echo is hidden through crossterm when built with the `tui` feature and
+/// through `stty` on other Unix builds. Without either the secret is echoed.
+fn read_hidden_line(input: &mut impl BufRead, line: &mut String) -> io::Result<usize> {
+    if !io::stdin().is_terminal() {
+        return input.read_line(line);
+    }
+    read_terminal_line(input, line)
+}
+
+#[cfg(feature = "tui")]
+fn read_terminal_line(_input: &mut impl BufRead, line: &mut String) -> io::Result<usize> {
+    guessing_game::tui::read_hidden_line(line)
+}
+
+#[cfg(all(unix, not(feature = "tui")))]
+fn read_terminal_line(input: &mut impl BufRead, line: &mut String) -> io::Result<usize> {
+    let hidden = set_echo(false);
+    let read = input.read_line(line);
+    if hidden {
+        set_echo(true);
+    }
+    read
+}
+
+#[cfg(not(any(unix, feature = "tui")))]
+fn read_terminal_line(input: &mut impl BufRead, line: &mut String) -> io::Result<usize> {
+    input.read_line(line)
+}
+
+#[cfg(all(unix, not(feature = "tui")))]
+fn set_echo(on: bool) -> bool {
+    process::Command::new("stty")
+        .arg(if on { "echo" } else { "-echo" })
+        .stdin(process::Stdio::inherit())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+fn run_multi(count: u32, args: PlayArgs, messages: &Messages) -> io::Result<ExitCode> {
+    let range = args.rules.range().map_err(invalid_input)?;
+    if !MultiGame::fits(&range, count as usize) {
+        return Err(invalid_input(format!(
+            "{count} distinct secrets don't fit between {} and {}",
+            range.start(),
+            range.end()
+        )));
+    }
+    if let Some(strategy) = args.auto.filter(|&strategy| strategy != Strategy::Binary) {
+        return Err(invalid_input(format!(
+            "the {strategy} bot can't play with --secrets, try --auto binary"
+        )));
+    }
+
+    let game = MultiGame::new(range, count as usize, args.secret_source());
+    let max_attempts = args.rules.max_attempts.or(args
+        .rules
+        .difficulty
+        .map(|difficulty| difficulty.max_attempts() * count));
+    let mut game = match max_attempts {
+        Some(max) => game.with_max_attempts(max),
+        None => game,
+    };
+
+    let mut output = io::stdout().lock();
+    if args.reveal {
+        let secrets = list(&game.secrets());
+        let line = messages.format("multi_secrets_are", &[("secrets", &secrets)]);
+        writeln!(output, "{line}")?;
+    }
+    let end = match args.auto {
+        Some(_) => auto_play_multi(&mut game, &mut output, messages)?,
+        None => play_multi(&mut game, &mut io::stdin().lock(), &mut output, messages)?,
+    };
+    Ok(end.exit_code())
+}
+
+fn play_multi(
+    game: &mut MultiGame,
+    input: &mut impl BufRead,
+    out: &mut impl Write,
+    messages: &Messages,
+) -> io::Result<GameEnd> {
+    let count = game.secrets().len();
+    writeln!(out, "{}", messages.text("welcome"))?;
+    writeln!(
+        out,
+        "{}",
+        messages.format(
+            "multi_intro",
+            &[
+                ("count", &count),
+                ("min", game.range().start()),
+                ("max", game.range().end()),
+            ],
+        )
+    )?;
+
+    while !game.is_over() {
+        let prompt = match game.remaining_attempts() {
+            Some(left) => messages.format("prompt_left", &[("left", &left)]),
+            None => messages.text("prompt").to_string(),
+        };
+        write!(out, "{prompt} ")?;
+        out.flush()?;
+
+        let mut line = String::new();
+        let parsed = match input.read_line(&mut line)? {
+            0 => Input::Quit,
+            _ => Input::parse(&line),
+        };
+        let guess = match parsed {
+            Input::Guess(guess) => guess,
+            Input::Quit => {
+                let secrets = list(&game.secrets());
+                let line = messages.format("multi_give_up", &[("secrets", &secrets)]);
+                writeln!(out, "{line}")?;
+                write_multi_summary(game, out, messages)?;
+                return Ok(GameEnd::Quit);
+            }
+            Input::Clue(_) => {
+                writeln!(out, "{}", messages.text("multi_no_clues"))?;
+                continue;
+            }
+            Input::Invalid(err) => {
+                writeln!(out, "{}", console::invalid(err, messages))?;
                 continue;
             }
         };
 
-        match guess.cmp(&secret) {
-            Ordering::Less => println!("Too small!"),
-            Ordering::Greater => println!("Too big!"),
-            Ordering::Equal => {
-                println!("You win!");
-                break;
+        let found = game.found().len();
+        match game.guess(guess) {
+            MultiOutcome::Counted(counted) => {
+                writeln!(out, "{}", console::count(counted, messages))?;
+                if game.found().len() > found {
+                    let line = messages.format(
+                        "multi_found",
+                        &[("found", &game.found().len()), ("count", &count)],
+                    );
+                    writeln!(out, "{line}")?;
+                }
+            }
+            MultiOutcome::OutOfRange => {
+                let line = messages.format(
+                    "out_of_range",
+                    &[
+                        ("guess", &guess),
+                        ("min", game.range().start()),
+                        ("max", game.range().end()),
+                    ],
+                );
+                writeln!(out, "{line}")?;
             }
+            MultiOutcome::GameOver => unreachable!("the loop stops once the game is over"),
         }
     }
+
+    finish_multi(game, out, messages)
+}
+
+fn auto_play_multi(
+    game: &mut MultiGame,
+    out: &mut impl Write,
+    messages: &Messages,
+) -> io::Result<GameEnd> {
+    let intro = messages.format(
+        "bot_intro",
+        &[
+            ("strategy", &Strategy::Binary),
+            ("min", game.range().start()),
+            ("max", game.range().end()),
+        ],
+    );
+    writeln!(out, "{intro}")?;
+
+    let mut bot = MultiSolver::new(game.range().clone(), game.secrets().len());
+    let transcript = multi::solve(game, &mut bot).map_err(io::Error::other)?;
+    for (guess, counted) in transcript {
+        let response = console::count(counted, messages);
+        let line = messages.format("bot_guess", &[("guess", &guess), ("response", &response)]);
+        writeln!(out, "{line}")?;
+    }
+
+    finish_multi(game, out, messages)
+}
+
+/// Announces how a finished game with several secrets ended.
+fn finish_multi(
+    game: &MultiGame,
+    out: &mut impl Write,
+    messages: &Messages,
+) -> io::Result<GameEnd> {
+    let end = if game.is_won() {
+        writeln!(out, "{}", messages.text("you_win"))?;
+        GameEnd::Won
+    } else {
+        let secrets = list(&game.secrets());
+        let line = messages.format("multi_lose", &[("secrets", &secrets)]);
+        writeln!(out, "{line}")?;
+        GameEnd::OutOfAttempts
+    };
+    write_multi_summary(game, out, messages)?;
+    Ok(end)
+}
+
+fn write_multi_summary(
+    game: &MultiGame,
+    out: &mut impl Write,
+    messages: &Messages,
+) -> io::Result<()> {
+    let attempts = game.attempts();
+    let summary = messages.format("summary_attempts", &[("attempts", &attempts)]);
+    writeln!(out, "{summary}")?;
+    if let Some(seed) = game.seed() {
+        writeln!(
+            out,
+            "{}",
+            messages.format("summary_seed", &[("seed", &seed)])
+        )?;
+    }
+    Ok(())
+}
+
+/// Numbers as a comma separated list.
+fn list(numbers: &[u64]) -> String {
+    let numbers: Vec<String> = numbers.iter().map(u64::to_string).collect();
+    numbers.join(", ")
+}
+
+fn play_hotseat(
+    hotseat: &mut HotSeat,
+    input: &mut impl BufRead,
+    out: &mut impl Write,
+    messages: &Messages,
+) -> io::Result<GameEnd> {
+    let names: Vec<&str> = hotseat.players().iter().map(|p| p.name.as_str()).collect();
+    writeln!(out, "{}", messages.text("welcome"))?;
+    writeln!(
+        out,
+        "{}",
+        messages.format(
+            "hotseat_intro",
+            &[
+                ("names", &names.join(", ")),
+                ("min", hotseat.game().range().start()),
+                ("max", hotseat.game().range().end()),
+            ],
+        )
+    )?;
+
+    while !hotseat.is_over() {
+        let name = hotseat.current().name.clone();
+        let prompt = match hotseat.remaining_attempts() {
+            Some(left) => {
+                messages.format("hotseat_prompt_left", &[("name", &name), ("left", &left)])
+            }
+            None => messages.format("hotseat_prompt", &[("name", &name)]),
+        };
+        write!(out, "{prompt} ")?;
+        out.flush()?;
+
+        let mut line = String::new();
+        let parsed = match input.read_line(&mut line)? {
+            0 => Input::Quit,
+            _ => Input::parse(&line),
+        };
+        let guess = match parsed {
+            Input::Guess(guess) => guess,
+            Input::Quit => {
+                writeln!(out, "{}", console::give_up(hotseat.game(), messages))?;
+                write_scoreboard(hotseat, out, messages)?;
+                return Ok(GameEnd::Quit);
+            }
+            Input::Clue(clue) => {
+                match hotseat.buy_clue(clue) {
+                    Some(answer) => {
+                        let answer = console::clue_answer(answer, messages);
+                        writeln!(
+                            out,
+                            "{}",
+                            messages.format("clue_bought", &[("answer", &answer)])
+                        )?;
+                    }
+                    None => writeln!(out, "{}", messages.text("clues_off"))?,
+                }
+                continue;
+            }
+            Input::Invalid(err) => {
+                writeln!(out, "{}", console::invalid(err, messages))?;
+                continue;
+            }
+        };
+
+        let outcome = hotseat.guess(guess);
+        if outcome != Outcome::Correct {
+            let response = console::describe(hotseat.game(), guess, outcome, messages);
+            writeln!(out, "{response}")?;
+        }
+        if matches!(outcome, Outcome::TooSmall | Outcome::TooBig) {
+            for hint in hotseat.game().hints() {
+                writeln!(out, "{}", console::hint(hint, messages))?;
+            }
+        }
+    }
+
+    let end = match hotseat.winner() {
+        Some(winner) => {
+            let winner = messages.format("hotseat_winner", &[("name", &winner.name)]);
+            writeln!(out, "{winner}")?;
+            GameEnd::Won
+        }
+        None => {
+            let secret = hotseat.game().secret();
+            let message = messages.format("hotseat_all_out", &[("secret", &secret)]);
+            writeln!(out, "{message}")?;
+            GameEnd::OutOfAttempts
+        }
+    };
+    write_scoreboard(hotseat, out, messages)?;
+    Ok(end)
+}
+
+fn write_scoreboard(
+    hotseat: &HotSeat,
+    out: &mut impl Write,
+    messages: &Messages,
+) -> io::Result<()> {
+    for player in hotseat.players() {
+        let line = messages.format(
+            "hotseat_score",
+            &[("name", &player.name), ("attempts", &player.attempts)],
+        );
+        writeln!(out, "  {line}")?;
+    }
+    if let Some(seed) = hotseat.game().seed() {
+        writeln!(
+            out,
+            "{}",
+            messages.format("summary_seed", &[("seed", &seed)])
+        )?;
+    }
+    Ok(())
+}
+
+fn default_player() -> String {
+    env::var("USER")
+        .or_else(|_| env::var("USERNAME"))
+        .unwrap_or_else(|_| "anonymous".to_string())
+}
+
+fn show_scores(args: ScoresArgs, messages: &Messages) -> io::Result<ExitCode> {
+    let path = scores::default_path()
+        .ok_or_else(|| io::Error::other("neither XDG_DATA_HOME nor HOME is set"))?;
+    let all = ScoreFile::new(path).load()?;
+
+    let difficulties = match args.difficulty {
+        Some(difficulty) => vec![Some(difficulty)],
+        None => Difficulty::ALL
+            .map(Some)
+            .into_iter()
+            .chain([None])
+            .collect(),
+    };
+
+    let tables = SecretKind::ALL.into_iter().flat_map(|kind| {
+        difficulties
+            .iter()
+            .map(move |&difficulty| (kind, difficulty))
+    });
+
+    let mut out = io::stdout().lock();
+    for (kind, difficulty) in tables {
+        let top = if args.speedrun {
+            scores::fastest(&all, kind, difficulty, args.limit)
+        } else {
+            scores::top(&all, kind, difficulty, args.limit)
+        };
+        if top.is_empty() {
+            continue;
+        }
+
+        let heading = match (kind, difficulty.map(|d| difficulty_name(d, messages))) {
+            (SecretKind::Number, Some(difficulty)) => {
+                messages.format("scores_difficulty", &[("difficulty", &difficulty)])
+            }
+            (SecretKind::Number, None) => messages.text("scores_custom").to_string(),
+            (kind, Some(difficulty)) => messages.format(
+                "scores_kind_difficulty",
+                &[
+                    ("kind", &kind_name(kind, messages)),
+                    ("difficulty", &difficulty),
+                ],
+            ),
+            (kind, None) => messages.format("scores_kind", &[("kind", &kind_name(kind, messages))]),
+        };
+        writeln!(out, "{heading}")?;
+        for (rank, score) in top.iter().enumerate() {
+            let row = messages.format(
+                "scores_row",
+                &[
+                    ("rank", &format!("{:>3}", rank + 1)),
+                    ("player", &format!("{:<16}", score.player)),
+                    ("attempts", &format!("{:>4}", score.attempts)),
+                    (
+                        "seconds",
+                        &format!("{:>8.1}", score.elapsed().as_secs_f64()),
+                    ),
+                    ("min", &score.min),
+                    ("max", &score.max),
+                ],
+            );
+            writeln!(out, "{row}")?;
+        }
+        writeln!(out)?;
+    }
+
+    Ok(ExitCode::SUCCESS)
+}
+
+fn difficulty_name(difficulty: Difficulty, messages: &Messages) -> &'static str {
+    messages.text(match difficulty {
+        Difficulty::Easy => "difficulty_easy",
+        Difficulty::Normal => "difficulty_normal",
+        Difficulty::Hard => "difficulty_hard",
+    })
+}
+
+fn kind_name(kind: SecretKind, messages: &Messages) -> &'static str {
+    messages.text(match kind {
+        SecretKind::Number => "kind_number",
+        SecretKind::Word => "kind_word",
+        SecretKind::Date => "kind_date",
+    })
+}
+
+fn simulate(args: SimulateArgs, messages: &Messages) -> io::Result<ExitCode> {
+    let range = args.rules.range().map_err(invalid_input)?;
+    let simulation = Simulation {
+        distribution: args.secrets.load(&range)?,
+        range,
+        max_attempts: args.rules.max_attempts(),
+        games: args.games,
+        seed: args.seed.unwrap_or_else(rand::random),
+    };
+    let strategies = if args.strategies.is_empty() {
+        Strategy::ALL.to_vec()
+    } else {
+        args.strategies
+    };
+
+    let stats = strategies
+        .into_iter()
+        .map(|strategy| simulation.run(strategy))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(io::Error::other)?;
+
+    let mut out = io::stdout().lock();
+    match args.format {
+        Format::Table => {
+            let intro = messages.format(
+                "simulate_intro",
+                &[
+                    ("games", &simulation.games),
+                    ("min", simulation.range.start()),
+                    ("max", simulation.range.end()),
+                    ("distribution", &simulation.distribution),
+                    ("seed", &simulation.seed),
+                ],
+            );
+            writeln!(out, "{intro}")?;
+            writeln!(out)?;
+            simulate::write_table(&mut out, &stats, messages)?;
+        }
+        Format::Csv => simulate::write_csv(&mut out, &stats)?,
+        Format::Json => simulate::write_json(&mut out, &stats)?,
+    }
+
+    Ok(ExitCode::SUCCESS)
+}
+
+fn serve(args: ServeArgs) -> io::Result<ExitCode> {
+    let config = ServerConfig {
+        range: args.rules.range().map_err(invalid_input)?,
+        max_attempts: args.rules.max_attempts(),
+        race: args.race,
+        idle_timeout: Some(args.idle_timeout),
+        max_clients: args.max_clients.into(),
+    };
+    let secrets = match args.seed {
+        Some(seed) => SeededSecret::new(seed),
+        None => SeededSecret::from_entropy(),
+    };
+
+    let server = Server::bind((args.host.as_str(), args.port), config, secrets)?;
+    eprintln!("Listening on {}", server.local_addr()?);
+    server.run()?;
+    Ok(ExitCode::SUCCESS)
+}
+
+fn reverse(args: ReverseArgs, messages: &Messages) -> io::Result<ExitCode> {
+    let range = args.rules.range().map_err(invalid_input)?;
+    let mut input = io::stdin().lock();
+    let mut output = io::stdout().lock();
+
+    let end = play_reverse(ReverseGame::new(range), &mut input, &mut output, messages)?;
+    Ok(end.exit_code())
+}
+
+fn play_reverse(
+    mut game: ReverseGame,
+    input: &mut impl BufRead,
+    out: &mut impl Write,
+    messages: &Messages,
+) -> io::Result<GameEnd> {
+    let intro = messages.format(
+        "reverse_intro",
+        &[("min", game.range().start()), ("max", game.range().end())],
+    );
+    writeln!(out, "{intro}")?;
+    writeln!(out, "{}", messages.text("reverse_instructions"))?;
+
+    while !game.is_found() {
+        let guess = match game.next_guess() {
+            Ok(guess) => guess,
+            Err(contradiction) => {
+                let contradiction = console::contradiction(contradiction, messages);
+                let message =
+                    messages.format("reverse_cheating", &[("contradiction", &contradiction)]);
+                writeln!(out, "{message}")?;
+                return Ok(GameEnd::Cheated);
+            }
+        };
+
+        let reply = loop {
+            write!(
+                out,
+                "{} ",
+                messages.format("reverse_ask", &[("guess", &guess)])
+            )?;
+            out.flush()?;
+
+            let mut line = String::new();
+            if input.read_line(&mut line)? == 0 || is_quit_command(line.trim()) {
+                writeln!(out, "{}", messages.text("reverse_give_up"))?;
+                return Ok(GameEnd::Quit);
+            }
+            match line.trim().parse::<Reply>() {
+                Ok(reply) => break reply,
+                Err(_) => {
+                    let reply = line.trim();
+                    let message = messages.format("reverse_bad_reply", &[("reply", &reply)]);
+                    writeln!(out, "{message}")?;
+                }
+            }
+        };
+        game.answer(guess, reply);
+    }
+
+    let found = messages.format("reverse_found", &[("attempts", &game.attempts())]);
+    writeln!(out, "{found}")?;
+    Ok(GameEnd::Won)
+}
+
+fn replay(args: ReplayArgs, messages: &Messages) -> io::Result<ExitCode> {
+    let file = File::open(&args.file).map_err(|err| with_path(&args.file, err))?;
+    let transcript =
+        Transcript::load(BufReader::new(file)).map_err(|err| with_path(&args.file, err))?;
+    let setup = &transcript.setup;
+
+    let mut out = io::stdout().lock();
+    let intro = messages.format(
+        "replay_intro",
+        &[
+            ("inputs", &transcript.entries.len()),
+            ("min", &setup.min),
+            ("max", &setup.max),
+            ("seed", &setup.seed),
+        ],
+    );
+    writeln!(out, "{intro}")?;
+
+    let steps = transcript.replay();
+    let mut mismatches = 0;
+    for (number, step) in steps.iter().enumerate() {
+        writeln!(out, "> {}", step.recorded.line)?;
+        for line in &step.replayed.response {
+            writeln!(out, "{line}")?;
+        }
+        if step.matches() {
+            continue;
+        }
+
+        mismatches += 1;
+        let mismatch = messages.format("replay_mismatch", &[("number", &(number + 1))]);
+        writeln!(out, "{mismatch}")?;
+        if step.recorded.parsed != step.replayed.parsed {
+            let parsed = messages.format(
+                "replay_parsed",
+                &[
+                    ("replayed", &format!("{:?}", step.replayed.parsed)),
+                    ("recorded", &format!("{:?}", step.recorded.parsed)),
+                ],
+            );
+            writeln!(out, "  {parsed}")?;
+        }
+        if step.recorded.response != step.replayed.response {
+            let response = format!("{:?}", step.recorded.response);
+            let recorded = messages.format("replay_recorded", &[("response", &response)]);
+            writeln!(out, "  {recorded}")?;
+        }
+    }
+
+    if mismatches > 0 {
+        let differ = messages.format(
+            "replay_differ",
+            &[("mismatches", &mismatches), ("inputs", &steps.len())],
+        );
+        writeln!(out, "{differ}")?;
+        return Ok(ExitCode::FAILURE);
+    }
+    let identical = messages.format("replay_identical", &[("inputs", &steps.len())]);
+    writeln!(out, "{identical}")?;
+    Ok(ExitCode::SUCCESS)
+}
+
+fn invalid_input(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, message)
+}
+
+fn with_path(path: &Path, err: io::Error) -> io::Error {
+    io::Error::new(err.kind(), format!("{}: {err}", path.display()))
+}
+
+/// Plays line by line on stdin, against the clock if a time limit is set.
+fn play_text<T: Guess>(
+    game: &mut Game<T>,
+    args: &PlayArgs,
+    out: &mut impl Write,
+    recorder: Option<&mut Recorder<File>>,
+    autosave: Option<&Autosave>,
+    messages: &Messages,
+) -> io::Result<GameEnd> {
+    match args.time_limits() {
+        Some(limits) => {
+            let countdown = Countdown::new(limits, SystemClock);
+            let lines = timer::spawn_lines(BufReader::new(io::stdin()));
+            play_timed(game, args.reveal, countdown, lines, out, messages)
+        }
+        None => play(
+            game,
+            args.reveal,
+            &mut io::stdin().lock(),
+            out,
+            recorder,
+            autosave,
+            messages,
+        ),
+    }
+}
+
+fn welcome<T: Guess>(
+    game: &Game<T>,
+    reveal: bool,
+    out: &mut impl Write,
+    messages: &Messages,
+) -> io::Result<()> {
+    if reveal {
+        let secret = messages.format(
+            &console::kind_key::<T>("secret_is"),
+            &[("secret", &game.secret())],
+        );
+        writeln!(out, "{secret}")?;
+    }
+
+    writeln!(out, "{}", messages.text("welcome"))?;
+    if let Some(lies) = game.max_lies() {
+        writeln!(
+            out,
+            "{}",
+            messages.format("lies_warning", &[("lies", &lies)])
+        )?;
+    }
+    let range = messages.format(
+        &console::kind_key::<T>("input_range"),
+        &[("min", game.range().start()), ("max", game.range().end())],
+    );
+    writeln!(out, "{range} ")
+}
+
+fn play<T: Guess>(
+    game: &mut Game<T>,
+    reveal: bool,
+    input: &mut impl BufRead,
+    out: &mut impl Write,
+    mut recorder: Option<&mut Recorder<File>>,
+    autosave: Option<&Autosave>,
+    messages: &Messages,
+) -> io::Result<GameEnd> {
+    welcome(game, reveal, out, messages)?;
+
+    loop {
+        if let Some(left) = game.remaining_attempts() {
+            write!(
+                out,
+                "{} ",
+                messages.format("prompt_left", &[("left", &left)])
+            )?;
+        }
+        out.flush()?;
+
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 {
+            // EOF, e.g. Ctrl-D or the end of a piped script.
+            return quit(game, out, messages);
+        }
+        let line = line.trim_end_matches(['\n', '\r']);
+        if is_save_command(line.trim()) {
+            let Some(autosave) = autosave else {
+                writeln!(out, "{}", messages.text("save_unavailable"))?;
+                continue;
+            };
+            autosave.save()?;
+            let saved = messages.format("game_saved", &[("path", &autosave.path.display())]);
+            writeln!(out, "{saved}")?;
+            return Ok(GameEnd::Saved);
+        }
+
+        let response = console::respond(game, line, messages);
+        for text in &response.lines {
+            writeln!(out, "{text}")?;
+        }
+        if let Some(autosave) = autosave {
+            autosave.record(line);
+        }
+        if let Some(recorder) = recorder.as_mut() {
+            recorder.record(&Entry::new(line, &response))?;
+        }
+
+        if response.input == Input::Quit {
+            write_summary(game, out, messages)?;
+            return Ok(GameEnd::Quit);
+        }
+        if let Some(end) = finish(game, out, messages)? {
+            return Ok(end);
+        }
+    }
+}
+
+/// Like `play`, but every guess and the game as a whole race a countdown.
+/// Lines come from a reader thread so waiting on them can time out.
+fn play_timed<T: Guess>(
+    game: &mut Game<T>,
+    reveal: bool,
+    mut countdown: Countdown,
+    lines: Receiver<io::Result<String>>,
+    out: &mut impl Write,
+    messages: &Messages,
+) -> io::Result<GameEnd> {
+    welcome(game, reveal, out, messages)?;
+    let limits = countdown.limits();
+    for (key, limit) in [
+        ("limit_per_guess", limits.per_guess),
+        ("limit_per_game", limits.per_game),
+    ] {
+        if let Some(limit) = limit {
+            let seconds = format!("{:.1}", limit.as_secs_f64());
+            writeln!(out, "{}", messages.format(key, &[("seconds", &seconds)]))?;
+        }
+    }
+
+    loop {
+        let clock = format!(
+            "{:.1}",
+            countdown.remaining().unwrap_or_default().as_secs_f64()
+        );
+        let prompt = match game.remaining_attempts() {
+            Some(left) => {
+                messages.format("prompt_timed_left", &[("left", &left), ("clock", &clock)])
+            }
+            None => messages.format("prompt_timed", &[("clock", &clock)]),
+        };
+        write!(out, "{prompt} ")?;
+        out.flush()?;
+
+        let wait = countdown.remaining().unwrap_or(Duration::MAX);
+        let expiry = match lines.recv_timeout(wait) {
+            // A line that arrives after the deadline is too late to count.
+            Ok(line) => match countdown.enforce(game) {
+                Some(expiry) => Some(expiry),
+                None => {
+                    let line = line?;
+                    if is_save_command(line.trim()) {
+                        writeln!(out, "{}", messages.text("save_unavailable"))?;
+                        continue;
+                    }
+                    let attempts = game.attempts();
+                    let response = console::respond(game, &line, messages);
+                    for text in &response.lines {
+                        writeln!(out, "{text}")?;
+                    }
+                    if response.input == Input::Quit {
+                        write_summary(game, out, messages)?;
+                        return Ok(GameEnd::Quit);
+                    }
+                    if game.attempts() != attempts {
+                        countdown.start_turn();
+                    }
+                    None
+                }
+            },
+            Err(RecvTimeoutError::Timeout) => {
+                writeln!(out)?;
+                countdown.enforce(game)
+            }
+            Err(RecvTimeoutError::Disconnected) => return quit(game, out, messages),
+        };
+
+        if expiry == Some(Expiry::Guess) {
+            writeln!(out, "{}", messages.text("guess_time_up"))?;
+        }
+        if expiry.is_some() && game.is_lost() {
+            writeln!(out, "{}", console::lose(game, messages))?;
+        }
+        if let Some(end) = finish(game, out, messages)? {
+            return Ok(end);
+        }
+    }
+}
+
+/// Lets a solver bot play, printing each of its guesses and the response.
+fn auto_play(
+    game: &mut Game,
+    strategy: Strategy,
+    distribution: &Distribution,
+    reveal: bool,
+    out: &mut impl Write,
+    messages: &Messages,
+) -> io::Result<GameEnd> {
+    let mut bot = match game.max_lies() {
+        Some(lies) if strategy == Strategy::Binary => {
+            Box::new(UlamSolver::new(game.range().clone(), lies))
+        }
+        Some(_) => {
+            return Err(invalid_input(format!(
+                "the {strategy} bot can't play with --lies, try --auto binary"
+            )));
+        }
+        None => strategy.solver_for(
+            game.range().clone(),
+            game.seed().unwrap_or_default(),
+            distribution,
+        ),
+    };
+
+    if reveal {
+        let secret = messages.format("secret_is", &[("secret", &game.secret())]);
+        writeln!(out, "{secret}")?;
+    }
+
+    let intro = messages.format(
+        "bot_intro",
+        &[
+            ("strategy", &strategy),
+            ("min", game.range().start()),
+            ("max", game.range().end()),
+        ],
+    );
+    writeln!(out, "{intro}")?;
+    if let Some(lies) = game.max_lies() {
+        writeln!(
+            out,
+            "{}",
+            messages.format("lies_warning", &[("lies", &lies)])
+        )?;
+    }
+
+    let transcript = solver::solve(game, bot.as_mut()).map_err(io::Error::other)?;
+    for turn in transcript {
+        let response = console::describe(game, turn.guess, turn.outcome, messages);
+        let line = messages.format(
+            "bot_guess",
+            &[("guess", &turn.guess), ("response", &response)],
+        );
+        writeln!(out, "{line}")?;
+    }
+    if game.is_lost() {
+        writeln!(out, "{}", console::lose(game, messages))?;
+    }
+
+    Ok(finish(game, out, messages)?.expect("solver stopped before the game was over"))
+}
+
+/// Writes the end-of-game summary once the game is won or lost.
+fn finish<T: Guess>(
+    game: &Game<T>,
+    out: &mut impl Write,
+    messages: &Messages,
+) -> io::Result<Option<GameEnd>> {
+    let Some(end) = GameEnd::of(game) else {
+        return Ok(None);
+    };
+
+    write_summary(game, out, messages)?;
+    Ok(Some(end))
+}
+
+/// Ends a game played by another front-end the same way `play` would.
+#[cfg(feature = "tui")]
+fn ended(game: &Game, out: &mut impl Write, messages: &Messages) -> io::Result<GameEnd> {
+    if game.is_won() {
+        writeln!(out, "{}", messages.text("you_win"))?;
+    } else if game.is_lost() {
+        writeln!(out, "{}", console::lose(game, messages))?;
+    }
+    match finish(game, out, messages)? {
+        Some(end) => Ok(end),
+        None => quit(game, out, messages),
+    }
+}
+
+fn quit<T: Guess>(
+    game: &Game<T>,
+    out: &mut impl Write,
+    messages: &Messages,
+) -> io::Result<GameEnd> {
+    writeln!(out, "{}", console::give_up(game, messages))?;
+    write_summary(game, out, messages)?;
+    Ok(GameEnd::Quit)
+}
+
+fn write_summary<T: Guess>(
+    game: &Game<T>,
+    out: &mut impl Write,
+    messages: &Messages,
+) -> io::Result<()> {
+    let attempts = game.attempts();
+    let summary = messages.format("summary_attempts", &[("attempts", &attempts)]);
+    writeln!(out, "{summary}")?;
+    if !game.hint_modes().is_empty() {
+        let modes: Vec<String> = game.hint_modes().iter().map(|m| m.to_string()).collect();
+        let hints = messages.format("summary_hints", &[("hints", &modes.join(", "))]);
+        writeln!(out, "{hints}")?;
+    }
+    if let Some(lies) = console::lies(game, messages) {
+        writeln!(out, "{lies}")?;
+    }
+    if let Some(seed) = game.seed() {
+        writeln!(
+            out,
+            "{}",
+            messages.format("summary_seed", &[("seed", &seed)])
+        )?;
+    }
+    Ok(())
 }
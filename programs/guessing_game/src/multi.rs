@@ -0,0 +1,321 @@
+//! Several secrets at once: every guess says how many secrets lie below it,
+//! at it and above it, and the game is won once each has been guessed.
+
+use std::cmp::Ordering;
+use std::collections::BTreeSet;
+use std::fmt;
+use std::ops::{Bound, RangeInclusive};
+
+use crate::solver::BinarySearch;
+use crate::{FixedSecret, Game, Outcome, SecretSource};
+
+/// Most secrets one game can hide, as it keeps a whole game for each.
+pub const MAX_SECRETS: usize = 10_000;
+
+/// How many secrets lie on each side of a guess. Found secrets still count.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Count {
+    pub below: usize,
+    /// 1 if the guess is a secret, 0 otherwise.
+    pub equal: usize,
+    pub above: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MultiOutcome {
+    Counted(Count),
+    OutOfRange,
+    /// The game was already won or lost; nothing was counted.
+    GameOver,
+}
+
+/// Distinct secrets in one range, each answered by a single-secret `Game`.
+#[derive(Debug)]
+pub struct MultiGame {
+    /// One game per secret, lowest secret first.
+    games: Vec<Game>,
+    range: RangeInclusive<u64>,
+    seed: Option<u64>,
+    attempts: u32,
+    max_attempts: Option<u32>,
+    guesses: Vec<u64>,
+}
+
+impl MultiGame {
+    /// Hides `count` distinct secrets in `range`, all drawn from
+    /// `secret_source`. Panics unless `count` is at most `MAX_SECRETS` and
+    /// `range` holds at least that many numbers.
+    pub fn new(
+        range: RangeInclusive<u64>,
+        count: usize,
+        mut secret_source: impl SecretSource,
+    ) -> Self {
+        assert!(
+            (1..=MAX_SECRETS).contains(&count),
+            "there must be between 1 and {MAX_SECRETS} secrets"
+        );
+        assert!(
+            Self::fits(&range, count),
+            "the range holds fewer than {count} numbers"
+        );
+
+        let mut secrets = BTreeSet::new();
+        let mut seed = None;
+        while secrets.len() < count {
+            secrets.insert(secret_source.pick(&range));
+            // Later secrets follow from the first seed, as in `SeededSecret`.
+            seed = seed.or(secret_source.seed());
+        }
+
+        Self {
+            games: secrets
+                .into_iter()
+                .map(|secret| Game::new(range.clone(), FixedSecret(secret)))
+                .collect(),
+            range,
+            seed,
+            attempts: 0,
+            max_attempts: None,
+            guesses: Vec::new(),
+        }
+    }
+
+    /// Whether `range` has room for `count` distinct secrets.
+    pub fn fits(range: &RangeInclusive<u64>, count: usize) -> bool {
+        !range.is_empty() && (range.end() - range.start()).saturating_add(1) >= count as u64
+    }
+
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = Some(max_attempts);
+        self
+    }
+
+    /// Counts the secrets on either side of `guess`. Guesses outside the
+    /// range are rejected and don't count as an attempt.
+    pub fn guess(&mut self, guess: u64) -> MultiOutcome {
+        if self.is_over() {
+            return MultiOutcome::GameOver;
+        }
+        if !self.range.contains(&guess) {
+            return MultiOutcome::OutOfRange;
+        }
+
+        self.attempts += 1;
+        self.guesses.push(guess);
+
+        let mut count = Count {
+            below: 0,
+            equal: 0,
+            above: 0,
+        };
+        for game in &mut self.games {
+            match compare(game, guess) {
+                Ordering::Less => count.above += 1,
+                Ordering::Equal => count.equal += 1,
+                Ordering::Greater => count.below += 1,
+            }
+        }
+        MultiOutcome::Counted(count)
+    }
+
+    pub fn range(&self) -> &RangeInclusive<u64> {
+        &self.range
+    }
+
+    /// Every secret, lowest first.
+    pub fn secrets(&self) -> Vec<u64> {
+        self.games.iter().map(Game::secret).collect()
+    }
+
+    /// The secrets guessed so far, lowest first.
+    pub fn found(&self) -> Vec<u64> {
+        self.games
+            .iter()
+            .filter(|game| game.is_won())
+            .map(Game::secret)
+            .collect()
+    }
+
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+
+    pub fn attempts(&self) -> u32 {
+        self.attempts
+    }
+
+    pub fn max_attempts(&self) -> Option<u32> {
+        self.max_attempts
+    }
+
+    pub fn remaining_attempts(&self) -> Option<u32> {
+        self.max_attempts
+            .map(|max| max.saturating_sub(self.attempts))
+    }
+
+    /// Every counted guess so far, oldest first.
+    pub fn guesses(&self) -> &[u64] {
+        &self.guesses
+    }
+
+    pub fn is_won(&self) -> bool {
+        self.games.iter().all(Game::is_won)
+    }
+
+    pub fn is_lost(&self) -> bool {
+        !self.is_won() && self.remaining_attempts() == Some(0)
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.is_won() || self.is_lost()
+    }
+}
+
+/// Where `guess` falls against `game`'s secret, asking the game unless its
+/// earlier answers or a win already tell.
+fn compare(game: &mut Game, guess: u64) -> Ordering {
+    if game.is_won() {
+        return guess.cmp(&game.secret());
+    }
+    match game.guess(guess) {
+        Outcome::TooSmall => Ordering::Less,
+        Outcome::TooBig => Ordering::Greater,
+        Outcome::Correct => Ordering::Equal,
+        Outcome::RuledOut => match game.bounds().0 {
+            Bound::Excluded(&above) if guess <= above => Ordering::Less,
+            _ => Ordering::Greater,
+        },
+        Outcome::OutOfRange | Outcome::GameOver => {
+            unreachable!("guesses are checked against the range and game first")
+        }
+    }
+}
+
+/// Reference bot: binary search inside every stretch still holding
+/// unfound secrets.
+///
+/// It keeps the stretches between its guesses, each with how many secrets
+/// it holds. A stretch holding as many secrets as numbers is guessed
+/// number by number; any other is split at its midpoint, which either finds
+/// a secret or leaves two stretches at most half as long. Each secret is
+/// therefore cornered within ⌊log2(n)⌋ + 1 guesses of its own, and all `k`
+/// are found within `k` times that, see [`MultiSolver::worst_case`].
+pub struct MultiSolver {
+    /// Stretches not guessed yet that still hold secrets, the one to
+    /// search next last.
+    stretches: Vec<Stretch>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Stretch {
+    low: u64,
+    high: u64,
+    secrets: usize,
+    /// Secrets below `low`.
+    before: usize,
+}
+
+impl MultiSolver {
+    pub fn new(range: RangeInclusive<u64>, count: usize) -> Self {
+        Self {
+            stretches: vec![Stretch {
+                low: *range.start(),
+                high: *range.end(),
+                secrets: count,
+                before: 0,
+            }],
+        }
+    }
+
+    /// Worst-case guesses needed for `count` secrets among `len` numbers.
+    pub fn worst_case(len: u64, count: usize) -> u32 {
+        count as u32 * BinarySearch::worst_case(len)
+    }
+
+    /// Next number to try, or `None` once every secret has been located.
+    pub fn next_guess(&self) -> Option<u64> {
+        let stretch = self.stretches.last()?;
+        if (stretch.high - stretch.low).saturating_add(1) == stretch.secrets as u64 {
+            Some(stretch.low)
+        } else {
+            Some(stretch.low + (stretch.high - stretch.low) / 2)
+        }
+    }
+
+    pub fn observe(&mut self, guess: u64, count: Count) {
+        let Some(index) = self
+            .stretches
+            .iter()
+            .position(|stretch| (stretch.low..=stretch.high).contains(&guess))
+        else {
+            return;
+        };
+        let stretch = self.stretches.remove(index);
+
+        let left = count.below.saturating_sub(stretch.before);
+        let right = stretch.secrets.saturating_sub(left + count.equal);
+        // `guess` is somewhere in the stretch, so only an end of the whole
+        // range can leave nothing on one side.
+        let halves = [
+            guess.checked_sub(1).map(|high| Stretch {
+                low: stretch.low,
+                high,
+                secrets: left,
+                before: stretch.before,
+            }),
+            guess.checked_add(1).map(|low| Stretch {
+                low,
+                high: stretch.high,
+                secrets: right,
+                before: stretch.before + left + count.equal,
+            }),
+        ];
+        for half in halves.into_iter().flatten() {
+            if half.secrets > 0 && half.low <= half.high {
+                self.stretches.insert(index, half);
+            }
+        }
+    }
+}
+
+/// The counts contradicted each other, leaving the solver nowhere to look
+/// before every secret was found.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Unsolved {
+    pub guesses: Vec<u64>,
+}
+
+impl fmt::Display for Unsolved {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "counts ruled out every number after {} guesses",
+            self.guesses.len()
+        )
+    }
+}
+
+impl std::error::Error for Unsolved {}
+
+/// Lets `solver` play `game` until it is over and returns every guess with
+/// its count.
+pub fn solve(
+    game: &mut MultiGame,
+    solver: &mut MultiSolver,
+) -> Result<Vec<(u64, Count)>, Unsolved> {
+    let mut turns = Vec::new();
+
+    while !game.is_over() {
+        let Some(guess) = solver.next_guess() else {
+            return Err(Unsolved {
+                guesses: game.guesses().to_vec(),
+            });
+        };
+        if let MultiOutcome::Counted(count) = game.guess(guess) {
+            solver.observe(guess, count);
+            turns.push((guess, count));
+        }
+    }
+
+    Ok(turns)
+}
@@ -0,0 +1,213 @@
+//! Reading guesses the way people type them: `42`, `1_000`, `0x2A`, `0b101`,
+//! `42.0` or `forty-two`.
+
+use std::fmt;
+use std::num::{IntErrorKind, ParseIntError};
+
+/// Why a line couldn't be read as a guess.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NumberError {
+    Empty,
+    Negative,
+    /// Bigger than any guess can be.
+    Overflow,
+    /// Has a non-zero fractional part, like `42.5`.
+    Fractional,
+    NotANumber,
+}
+
+impl fmt::Display for NumberError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let message = match self {
+            NumberError::Empty => "nothing was entered",
+            NumberError::Negative => "negative numbers are never the secret",
+            NumberError::Overflow => "that number is too large",
+            NumberError::Fractional => "the secret is a whole number",
+            NumberError::NotANumber => "that's not a number",
+        };
+        f.write_str(message)
+    }
+}
+
+impl std::error::Error for NumberError {}
+
+impl From<ParseIntError> for NumberError {
+    fn from(err: ParseIntError) -> Self {
+        match err.kind() {
+            IntErrorKind::Empty => NumberError::Empty,
+            IntErrorKind::PosOverflow => NumberError::Overflow,
+            IntErrorKind::NegOverflow => NumberError::Negative,
+            _ => NumberError::NotANumber,
+        }
+    }
+}
+
+/// Parses a guess written as a decimal, `0x`/`0o`/`0b` prefixed literal or in
+/// English words. Underscores between digits are ignored as in Rust, and a
+/// decimal point is fine as long as only zeros follow it.
+pub fn parse(input: &str) -> Result<u64, NumberError> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Err(NumberError::Empty);
+    }
+
+    if let Some(rest) = input.strip_prefix('-') {
+        // "-0" is still zero; anything else numeric is a negative guess.
+        return match parse(rest) {
+            Ok(0) => Ok(0),
+            Ok(_) | Err(NumberError::Overflow | NumberError::Fractional) => {
+                Err(NumberError::Negative)
+            }
+            Err(NumberError::Empty) => Err(NumberError::NotANumber),
+            Err(err) => Err(err),
+        };
+    }
+    let input = input.strip_prefix('+').unwrap_or(input);
+
+    if input.starts_with(|c: char| c.is_ascii_digit()) {
+        parse_literal(input)
+    } else {
+        parse_words(input).ok_or(NumberError::NotANumber)?
+    }
+}
+
+fn parse_literal(input: &str) -> Result<u64, NumberError> {
+    let lower = input.to_ascii_lowercase();
+    let (digits, radix) = match lower.get(..2) {
+        Some("0x") => (&lower[2..], 16),
+        Some("0o") => (&lower[2..], 8),
+        Some("0b") => (&lower[2..], 2),
+        _ => (lower.as_str(), 10),
+    };
+    if digits.starts_with('_') {
+        return Err(NumberError::NotANumber);
+    }
+    let digits = digits.replace('_', "");
+
+    if radix == 10
+        && let Some((whole, fraction)) = digits.split_once('.')
+    {
+        if fraction.is_empty() || !fraction.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(NumberError::NotANumber);
+        }
+        let whole: u64 = whole.parse()?;
+        if fraction.bytes().all(|b| b == b'0') {
+            return Ok(whole);
+        }
+        return Err(NumberError::Fractional);
+    }
+
+    Ok(u64::from_str_radix(&digits, radix)?)
+}
+
+const ONES: [&str; 20] = [
+    "zero",
+    "one",
+    "two",
+    "three",
+    "four",
+    "five",
+    "six",
+    "seven",
+    "eight",
+    "nine",
+    "ten",
+    "eleven",
+    "twelve",
+    "thirteen",
+    "fourteen",
+    "fifteen",
+    "sixteen",
+    "seventeen",
+    "eighteen",
+    "nineteen",
+];
+
+const TENS: [&str; 8] = [
+    "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety",
+];
+
+const SCALES: [(&str, u64); 6] = [
+    ("thousand", 1_000),
+    ("million", 1_000_000),
+    ("billion", 1_000_000_000),
+    ("trillion", 1_000_000_000_000),
+    ("quadrillion", 1_000_000_000_000_000),
+    ("quintillion", 1_000_000_000_000_000_000),
+];
+
+/// English number words such as "one hundred and five" or "forty-two".
+/// `None` if any word isn't part of a number; `Some(Err)` if the words are
+/// out of order or make a number that can't be a guess.
+fn parse_words(input: &str) -> Option<Result<u64, NumberError>> {
+    let words: Vec<String> = input
+        .split(|c: char| c.is_whitespace() || c == '-')
+        .filter(|word| !word.is_empty())
+        .map(str::to_ascii_lowercase)
+        .collect();
+    if let Some(first) = words.first()
+        && (first == "minus" || first == "negative")
+    {
+        return match parse_words(&words[1..].join(" "))? {
+            Ok(0) => Some(Ok(0)),
+            Ok(_) | Err(NumberError::Overflow) => Some(Err(NumberError::Negative)),
+            Err(err) => Some(Err(err)),
+        };
+    }
+
+    // Words go in groups of up to "nine hundred and ninety-nine", each ended
+    // by a scale word smaller than the one before. Only the first number
+    // word may stand for an unspoken "one", as in "a hundred" or "thousand".
+    // `total` is `None` once the number has grown too large for a guess.
+    let mut total = Some(0u64);
+    let (mut hundreds, mut tens, mut ones) = (None, None, None);
+    let mut last_scale = u64::MAX;
+    let mut first = true;
+    for word in &words {
+        let word = word.as_str();
+        if word == "and" || word == "a" {
+            continue;
+        }
+
+        let fits = if let Some(n) = ONES.iter().position(|&w| w == word) {
+            let n = n as u64;
+            let fits =
+                ones.is_none() && (tens.is_none() || (1..=9).contains(&n)) && (n > 0 || first);
+            ones = Some(n);
+            fits
+        } else if let Some(n) = TENS.iter().position(|&w| w == word) {
+            let fits = tens.is_none() && ones.is_none();
+            tens = Some((n as u64 + 2) * 10);
+            fits
+        } else if word == "hundred" {
+            let fits = hundreds.is_none()
+                && tens.is_none()
+                && ones.map_or(first, |n| (1..=9).contains(&n));
+            hundreds = Some(ones.take().unwrap_or(1) * 100);
+            fits
+        } else if let Some(&(_, scale)) = SCALES.iter().find(|(w, _)| *w == word) {
+            let group = [hundreds.take(), tens.take(), ones.take()]
+                .into_iter()
+                .flatten()
+                .sum::<u64>();
+            let fits = scale < last_scale && (group > 0 || first);
+            let value = group.max(1).checked_mul(scale);
+            total = total.zip(value).and_then(|(t, v)| t.checked_add(v));
+            last_scale = scale;
+            fits
+        } else {
+            return None;
+        };
+        if !fits {
+            return Some(Err(NumberError::NotANumber));
+        }
+        first = false;
+    }
+
+    if first {
+        return None;
+    }
+    let group = [hundreds, tens, ones].into_iter().flatten().sum::<u64>();
+    let value = total.and_then(|t| t.checked_add(group));
+    Some(value.ok_or(NumberError::Overflow))
+}
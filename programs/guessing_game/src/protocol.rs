@@ -0,0 +1,213 @@
+//! The guessing game for bots over stdin and stdout, one JSON object per line.
+//!
+//! The game opens with a `start` event. Clients then send requests and get
+//! one event back for each, until an `end` event closes the game:
+//!
+//! ```text
+//! {"event":"start","version":1,"min":1,"max":100,"max_attempts":null,"max_lies":null}
+//! > {"guess":50}
+//! {"event":"result","result":"high","attempts":1,"remaining":null}
+//! > {"guess":"fifty"}
+//! {"event":"error","kind":"request","message":"invalid type: string \"fifty\", expected u64 at line 1 column 16"}
+//! > {"guess":25}
+//! {"event":"result","result":"correct","attempts":2,"remaining":null}
+//! {"event":"end","outcome":"won","secret":25,"attempts":2,"lies":[]}
+//! ```
+//!
+//! | Request         | Meaning                      |
+//! |-----------------|------------------------------|
+//! | `{"guess":<n>}` | guess the number `n`         |
+//! | `{"quit":null}` | give up and end the game now |
+//!
+//! A `result` is one of `low` (the guess is below the secret), `high`,
+//! `correct`, `out_of_range` or `ruled_out`; the last two don't tell you
+//! anything new. `remaining` is how many attempts are left, or `null` when
+//! they aren't limited. An `error` of kind `parse` is a line that isn't JSON,
+//! and one of kind `request` is JSON that isn't a request. Neither costs an
+//! attempt. The `end` event's `outcome` is `won`, `lost` or `quit`, and
+//! `lies` lists the guesses whose answer was a lie when `max_lies` isn't
+//! `null`. Closing stdin quits.
+//!
+//! `version` only changes when a change would break existing clients; new
+//! fields may be added to events without bumping it.
+
+use serde::{Deserialize, Serialize};
+
+use std::io::{self, BufRead, Write};
+
+use crate::{Game, Outcome};
+
+/// Bumped whenever a change to the protocol would break existing clients.
+pub const VERSION: u32 = 1;
+
+/// One line sent by the client.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Request {
+    Guess(u64),
+    Quit,
+}
+
+/// One line sent back to the client.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum Event {
+    Start {
+        version: u32,
+        min: u64,
+        max: u64,
+        max_attempts: Option<u32>,
+        max_lies: Option<u32>,
+        /// Only sent when the secret is revealed up front.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        secret: Option<u64>,
+    },
+    Result {
+        result: Verdict,
+        attempts: u32,
+        remaining: Option<u32>,
+    },
+    Error {
+        kind: ErrorKind,
+        message: String,
+    },
+    End {
+        outcome: Ending,
+        secret: u64,
+        attempts: u32,
+        lies: Vec<u64>,
+    },
+}
+
+/// What a guess turned out to be, named from the guess's point of view.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Verdict {
+    Low,
+    High,
+    Correct,
+    OutOfRange,
+    RuledOut,
+    GameOver,
+}
+
+impl From<Outcome> for Verdict {
+    fn from(outcome: Outcome) -> Self {
+        match outcome {
+            Outcome::TooSmall => Verdict::Low,
+            Outcome::TooBig => Verdict::High,
+            Outcome::Correct => Verdict::Correct,
+            Outcome::OutOfRange => Verdict::OutOfRange,
+            Outcome::RuledOut => Verdict::RuledOut,
+            Outcome::GameOver => Verdict::GameOver,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorKind {
+    /// The line isn't JSON.
+    Parse,
+    /// The line is JSON, but not a request.
+    Request,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Ending {
+    Won,
+    Lost,
+    Quit,
+}
+
+impl Event {
+    pub fn start(game: &Game, reveal: bool) -> Event {
+        Event::Start {
+            version: VERSION,
+            min: *game.range().start(),
+            max: *game.range().end(),
+            max_attempts: game.max_attempts(),
+            max_lies: game.max_lies(),
+            secret: reveal.then(|| game.secret()),
+        }
+    }
+
+    pub fn result(game: &Game, outcome: Outcome) -> Event {
+        Event::Result {
+            result: outcome.into(),
+            attempts: game.attempts(),
+            remaining: game.remaining_attempts(),
+        }
+    }
+
+    fn error(err: &serde_json::Error) -> Event {
+        let kind = if err.is_data() {
+            ErrorKind::Request
+        } else {
+            ErrorKind::Parse
+        };
+        Event::Error {
+            kind,
+            message: err.to_string(),
+        }
+    }
+
+    /// How `game` ended; games still going when the client left were quit.
+    pub fn end(game: &Game) -> Event {
+        let outcome = if game.is_won() {
+            Ending::Won
+        } else if game.is_lost() {
+            Ending::Lost
+        } else {
+            Ending::Quit
+        };
+        Event::End {
+            outcome,
+            secret: game.secret(),
+            attempts: game.attempts(),
+            lies: game.lies().iter().map(|&i| game.guesses()[i]).collect(),
+        }
+    }
+}
+
+/// Plays `game` with whoever is on the other end of `input` and `out` until
+/// it's over, they quit or `input` runs out.
+pub fn play(
+    game: &mut Game,
+    input: impl BufRead,
+    mut out: impl Write,
+    reveal: bool,
+) -> io::Result<()> {
+    send(&mut out, &Event::start(game, reveal))?;
+
+    for line in input.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        match serde_json::from_str(&line) {
+            Ok(Request::Guess(guess)) => {
+                let outcome = game.guess(guess);
+                send(&mut out, &Event::result(game, outcome))?;
+            }
+            Ok(Request::Quit) => break,
+            Err(err) => send(&mut out, &Event::error(&err))?,
+        }
+        if game.is_over() {
+            break;
+        }
+    }
+
+    send(&mut out, &Event::end(game))
+}
+
+/// Writes one event and flushes it, so a client waiting on it isn't left
+/// hanging behind a buffer.
+fn send(out: &mut impl Write, event: &Event) -> io::Result<()> {
+    let mut line = serde_json::to_string(event)?;
+    line.push('\n');
+    out.write_all(line.as_bytes())?;
+    out.flush()
+}
@@ -0,0 +1,105 @@
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+use crate::Outcome;
+use crate::solver::{BinarySearch, Solver};
+
+/// The player's answer to one of the computer's guesses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reply {
+    /// The player's number is higher than the guess.
+    Higher,
+    /// The player's number is lower than the guess.
+    Lower,
+    Correct,
+}
+
+impl FromStr for Reply {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "higher" | "h" | "+" => Ok(Reply::Higher),
+            "lower" | "l" | "-" => Ok(Reply::Lower),
+            "correct" | "c" | "yes" | "y" | "=" => Ok(Reply::Correct),
+            _ => Err(format!(
+                "'{s}' isn't a reply (expected higher, lower or correct)"
+            )),
+        }
+    }
+}
+
+/// Answers that no number in the range can satisfy at once.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Contradiction {
+    /// The largest guess the player said their number was higher than.
+    pub higher_than: Option<u64>,
+    /// The smallest guess the player said their number was lower than.
+    pub lower_than: Option<u64>,
+    pub range: (u64, u64),
+}
+
+/// The computer guesses a number the player is thinking of.
+pub struct ReverseGame {
+    range: RangeInclusive<u64>,
+    solver: BinarySearch,
+    higher_than: Option<u64>,
+    lower_than: Option<u64>,
+    attempts: u32,
+    found: bool,
+}
+
+impl ReverseGame {
+    pub fn new(range: RangeInclusive<u64>) -> Self {
+        Self {
+            solver: BinarySearch::new(range.clone()),
+            range,
+            higher_than: None,
+            lower_than: None,
+            attempts: 0,
+            found: false,
+        }
+    }
+
+    /// The computer's next guess, or the contradiction that leaves it
+    /// nothing to guess.
+    pub fn next_guess(&mut self) -> Result<u64, Contradiction> {
+        self.solver.next_guess().ok_or(Contradiction {
+            higher_than: self.higher_than,
+            lower_than: self.lower_than,
+            range: (*self.range.start(), *self.range.end()),
+        })
+    }
+
+    pub fn answer(&mut self, guess: u64, reply: Reply) {
+        self.attempts += 1;
+
+        let outcome = match reply {
+            Reply::Higher => {
+                self.higher_than = self.higher_than.max(Some(guess));
+                Outcome::TooSmall
+            }
+            Reply::Lower => {
+                self.lower_than = Some(self.lower_than.map_or(guess, |low| low.min(guess)));
+                Outcome::TooBig
+            }
+            Reply::Correct => {
+                self.found = true;
+                Outcome::Correct
+            }
+        };
+        self.solver.observe(guess, outcome);
+    }
+
+    pub fn range(&self) -> &RangeInclusive<u64> {
+        &self.range
+    }
+
+    pub fn attempts(&self) -> u32 {
+        self.attempts
+    }
+
+    pub fn is_found(&self) -> bool {
+        self.found
+    }
+}
@@ -0,0 +1,230 @@
+//! Games saved part way through, to be picked up again with `--resume`.
+//!
+//! A save is one JSON object holding the rules, every line typed so far and
+//! the time already played. Resuming replays those lines into a fresh game,
+//! so it ends up exactly where the player left it.
+//!
+//! The secret and its seed are sealed, XORed with a keystream derived from a
+//! per-save nonce, so they can't be read straight off the file, and the whole
+//! file is signed with HMAC-SHA256 so editing anything in it, sealed secret
+//! included, is caught on load. The key ships inside the binary: this keeps
+//! honest players honest, it doesn't stop someone reading the source.
+
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+
+use std::fs::{self, File};
+use std::io::{self, Read, Write};
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::console;
+use crate::hints::HintMode;
+use crate::i18n::{Locale, Messages};
+use crate::scores;
+use crate::ulam::Liar;
+use crate::{Difficulty, Game, SecretSource};
+
+/// Bumped whenever a change to the format would stop old saves loading.
+pub const VERSION: u32 = 1;
+
+const KEY: &[u8] = b"guessing_game saved games, v1";
+
+/// `save.json` next to the score file.
+pub fn default_path() -> Option<PathBuf> {
+    Some(scores::data_dir()?.join("save.json"))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SavedGame {
+    pub version: u32,
+    pub min: u64,
+    pub max: u64,
+    pub max_attempts: Option<u32>,
+    pub hints: Vec<HintMode>,
+    pub free_repeats: bool,
+    pub max_lies: Option<u32>,
+    /// The preset the game was started with, for the score table.
+    pub difficulty: Option<Difficulty>,
+    /// Every line typed so far, oldest first.
+    pub inputs: Vec<String>,
+    pub elapsed_ms: u64,
+    nonce: u64,
+    /// The secret and its seed, sealed, in hex.
+    sealed: String,
+    /// HMAC-SHA256 of the rest of the file, in hex.
+    tag: String,
+}
+
+impl SavedGame {
+    /// `None` if `game` wasn't seeded. Lines already played are not
+    /// included, `game` should be fresh or have them added with `record`.
+    pub fn of(game: &Game, difficulty: Option<Difficulty>) -> Option<SavedGame> {
+        let seed = game.seed()?;
+        let nonce = rand::random();
+        let mut secrets = [0; 16];
+        secrets[..8].copy_from_slice(&game.secret().to_le_bytes());
+        secrets[8..].copy_from_slice(&seed.to_le_bytes());
+
+        Some(SavedGame {
+            version: VERSION,
+            min: *game.range().start(),
+            max: *game.range().end(),
+            max_attempts: game.max_attempts(),
+            hints: game.hint_modes().to_vec(),
+            free_repeats: game.free_repeats(),
+            max_lies: game.max_lies(),
+            difficulty,
+            inputs: Vec::new(),
+            elapsed_ms: 0,
+            nonce,
+            sealed: to_hex(&seal(nonce, secrets)),
+            tag: String::new(),
+        })
+    }
+
+    /// Adds a line the player typed since the game was saved.
+    pub fn record(&mut self, line: &str) {
+        self.inputs.push(line.to_string());
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        Duration::from_millis(self.elapsed_ms)
+    }
+
+    pub fn set_elapsed(&mut self, elapsed: Duration) {
+        self.elapsed_ms = elapsed.as_millis() as u64;
+    }
+
+    /// Rebuilds the game and replays every recorded line into it.
+    pub fn game(&self) -> io::Result<Game> {
+        let secrets = from_hex(&self.sealed)
+            .and_then(|sealed| <[u8; 16]>::try_from(sealed).ok())
+            .map(|sealed| seal(self.nonce, sealed))
+            .ok_or_else(|| invalid("the sealed secret is malformed"))?;
+        let secret = u64::from_le_bytes(secrets[..8].try_into().unwrap());
+        let seed = u64::from_le_bytes(secrets[8..].try_into().unwrap());
+        if !(self.min..=self.max).contains(&secret) {
+            return Err(invalid("the secret is outside the range"));
+        }
+
+        let game = Game::new(self.min..=self.max, Unsealed { secret, seed })
+            .with_hints(self.hints.iter().copied())
+            .with_free_repeats(self.free_repeats);
+        let game = match self.max_attempts {
+            Some(max) => game.with_max_attempts(max),
+            None => game,
+        };
+        let mut game = match self.max_lies {
+            Some(lies) => game.with_lies(Liar::new(lies, seed)),
+            None => game,
+        };
+
+        // Only the game's state matters here, not the words it answers in.
+        let messages = Messages::new(Locale::En);
+        for line in &self.inputs {
+            console::respond(&mut game, line, &messages);
+        }
+        if game.is_over() {
+            return Err(invalid("the saved game is already over"));
+        }
+        Ok(game)
+    }
+
+    /// Writes the signed save to `path`, replacing any earlier one in a
+    /// single rename so a crash mid-write leaves the old save intact.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        let temporary = path.with_extension("tmp");
+        self.write(File::create(&temporary)?)?;
+        fs::rename(&temporary, path)
+    }
+
+    /// Writes the signed save as one line of JSON.
+    pub fn write(&self, mut out: impl Write) -> io::Result<()> {
+        let mut signed = self.clone();
+        signed.tag = self.signature()?;
+        let mut line = serde_json::to_string(&signed)?;
+        line.push('\n');
+        out.write_all(line.as_bytes())?;
+        out.flush()
+    }
+
+    /// Reads a save, rejecting anything malformed, from another version or
+    /// edited since it was written with `InvalidData`.
+    pub fn load(mut input: impl Read) -> io::Result<SavedGame> {
+        let mut json = String::new();
+        input.read_to_string(&mut json)?;
+        let saved: SavedGame = serde_json::from_str(&json).map_err(io::Error::from)?;
+
+        if saved.version != VERSION {
+            return Err(invalid(&format!(
+                "save version {} is not supported (expected {VERSION})",
+                saved.version
+            )));
+        }
+        if saved.tag != saved.signature()? {
+            return Err(invalid("the save has been tampered with"));
+        }
+        Ok(saved)
+    }
+
+    fn signature(&self) -> io::Result<String> {
+        let unsigned = SavedGame {
+            tag: String::new(),
+            ..self.clone()
+        };
+        let json = serde_json::to_vec(&unsigned)?;
+        Ok(to_hex(&hmac_sha256::HMAC::mac(json, KEY)))
+    }
+}
+
+/// Hands back the secret and seed a save was sealed with.
+struct Unsealed {
+    secret: u64,
+    seed: u64,
+}
+
+impl SecretSource for Unsealed {
+    fn pick(&mut self, _range: &RangeInclusive<u64>) -> u64 {
+        self.secret
+    }
+
+    fn seed(&self) -> Option<u64> {
+        Some(self.seed)
+    }
+}
+
+/// XORs `bytes` with the keystream for `nonce`; sealing twice unseals.
+fn seal(nonce: u64, mut bytes: [u8; 16]) -> [u8; 16] {
+    let mut keystream = ChaCha8Rng::from_seed(hmac_sha256::HMAC::mac(nonce.to_le_bytes(), KEY));
+    let mut pad = [0; 16];
+    keystream.fill(&mut pad);
+    for (byte, pad) in bytes.iter_mut().zip(pad) {
+        *byte ^= pad;
+    }
+    bytes
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+fn from_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) || !hex.is_ascii() {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).ok())
+        .collect()
+}
+
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.to_string())
+}
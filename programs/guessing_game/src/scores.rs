@@ -0,0 +1,151 @@
+use serde::{Deserialize, Serialize};
+
+use std::env;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::{Difficulty, SecretKind};
+
+/// One finished game, as stored in the scores file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Score {
+    pub player: String,
+    pub won: bool,
+    pub attempts: u32,
+    pub elapsed_ms: u64,
+    /// For words and dates, positions in the list the secret came from.
+    pub min: u64,
+    pub max: u64,
+    #[serde(default)]
+    pub kind: SecretKind,
+    /// `None` for games played with a custom `--min`/`--max` range.
+    pub difficulty: Option<Difficulty>,
+    /// Seconds since the Unix epoch when the game ended.
+    pub finished_at: u64,
+}
+
+impl Score {
+    pub fn elapsed(&self) -> Duration {
+        Duration::from_millis(self.elapsed_ms)
+    }
+
+    /// Fewer attempts wins, ties go to the faster game.
+    fn rank_key(&self) -> (u32, u64) {
+        (self.attempts, self.elapsed_ms)
+    }
+}
+
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+/// `$XDG_DATA_HOME/guessing_game/scores.jsonl`.
+pub fn default_path() -> Option<PathBuf> {
+    Some(data_dir()?.join("scores.jsonl"))
+}
+
+/// `$XDG_DATA_HOME/guessing_game`, falling back to `~/.local/share` when
+/// `XDG_DATA_HOME` is unset.
+pub fn data_dir() -> Option<PathBuf> {
+    let data_home = env::var_os("XDG_DATA_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".local/share")))?;
+
+    Some(data_home.join("guessing_game"))
+}
+
+/// An append-only JSON-lines file of scores.
+///
+/// Every write appends one whole line while holding an exclusive lock, so
+/// games finishing in several terminals at once never interleave.
+pub struct ScoreFile {
+    path: PathBuf,
+}
+
+impl ScoreFile {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn record(&self, score: &Score) -> io::Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        let mut line = serde_json::to_string(score)?;
+        line.push('\n');
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        file.lock()?;
+        let written = file.write_all(line.as_bytes());
+        file.unlock()?;
+        written
+    }
+
+    /// Every readable score. Lines that don't parse, such as one cut short
+    /// by a crash, are skipped rather than failing the whole file.
+    pub fn load(&self) -> io::Result<Vec<Score>> {
+        let file = match File::open(&self.path) {
+            Ok(file) => file,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(err),
+        };
+        file.lock_shared()?;
+
+        let mut scores = Vec::new();
+        for line in BufReader::new(&file).lines() {
+            if let Ok(score) = serde_json::from_str(&line?) {
+                scores.push(score);
+            }
+        }
+
+        file.unlock()?;
+        Ok(scores)
+    }
+}
+
+/// The best `limit` wins for one kind of secret and difficulty, best first.
+pub fn top(
+    scores: &[Score],
+    kind: SecretKind,
+    difficulty: Option<Difficulty>,
+    limit: usize,
+) -> Vec<&Score> {
+    let mut wins = wins(scores, kind, difficulty);
+    wins.sort_by_key(|score| score.rank_key());
+    wins.truncate(limit);
+    wins
+}
+
+/// The speedrun table: the quickest wins by wall-clock time, ties going to
+/// fewer attempts.
+pub fn fastest(
+    scores: &[Score],
+    kind: SecretKind,
+    difficulty: Option<Difficulty>,
+    limit: usize,
+) -> Vec<&Score> {
+    let mut wins = wins(scores, kind, difficulty);
+    wins.sort_by_key(|score| (score.elapsed_ms, score.attempts));
+    wins.truncate(limit);
+    wins
+}
+
+fn wins(scores: &[Score], kind: SecretKind, difficulty: Option<Difficulty>) -> Vec<&Score> {
+    scores
+        .iter()
+        .filter(|score| score.won && score.kind == kind && score.difficulty == difficulty)
+        .collect()
+}
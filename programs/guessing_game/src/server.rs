@@ -0,0 +1,406 @@
+//! The guessing game over TCP, one line per message.
+//!
+//! On connect the server sends `HELLO <min> <max>`, followed by
+//! ` <max_attempts>` when attempts are limited. Clients then send commands,
+//! case-insensitively, and get one reply line each unless noted below:
+//!
+//! | Command       | Reply                                                      |
+//! |---------------|------------------------------------------------------------|
+//! | `GUESS <n>`   | `LOW`, `HIGH`, `WIN <attempts>`, `RULEDOUT`, `OUT`, `LOSE` |
+//! | `NAME <name>` | `OK`                                                       |
+//! | `NEW`         | a fresh `HELLO` line (independent games only)              |
+//! | `QUIT`        | `BYE`, then the server closes the connection               |
+//!
+//! `LOW` means the guess is below the secret and `HIGH` above it. `OUT` is a
+//! guess outside `min..=max` and `RULEDOUT` one that earlier answers already
+//! exclude; neither tells you anything new. `LOSE` answers the guess that used
+//! the last attempt. In independent games `LOSE` and `BYE` carry the secret,
+//! as in `LOSE <secret>`, while races keep it hidden until the round is over.
+//! Anything malformed gets `ERR <message>`.
+//!
+//! In a race every client guesses the same secret. The first `WIN` ends the
+//! round for everybody: the next command anyone else sends is answered with
+//! `OVER <winner> <secret>` and a `HELLO` for the next round. Attempts are
+//! counted per name, so reconnecting or sharing a name doesn't get a player
+//! any more of them. Once every connected player is out of attempts the round
+//! ends without a winner, reported as `OVER - <secret>`.
+//!
+//! A client that sends nothing for the configured idle timeout gets
+//! `ERR idle timeout` and is disconnected. Once the configured number of
+//! clients are connected, anyone else gets `ERR server full` instead of
+//! `HELLO` and is disconnected straight away.
+
+use std::collections::HashMap;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::ops::RangeInclusive;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::thread;
+use std::time::Duration;
+
+use crate::{FixedSecret, Game, Outcome, SecretSource};
+
+#[derive(Debug, Clone)]
+pub struct ServerConfig {
+    pub range: RangeInclusive<u64>,
+    pub max_attempts: Option<u32>,
+    /// Every client races for one shared secret instead of playing alone.
+    pub race: bool,
+    /// How long a client may stay silent before it is disconnected.
+    pub idle_timeout: Option<Duration>,
+    /// Clients served at once, each on its own thread.
+    pub max_clients: usize,
+}
+
+pub struct Server {
+    listener: TcpListener,
+    shared: Arc<Shared>,
+}
+
+struct Shared {
+    config: ServerConfig,
+    secrets: Mutex<Box<dyn SecretSource + Send>>,
+    race: Mutex<Race>,
+    /// Clients currently connected.
+    clients: AtomicUsize,
+}
+
+/// The round everyone is currently racing in.
+struct Race {
+    round: u64,
+    secret: u64,
+    /// Everyone who has joined this round, by name.
+    players: HashMap<String, Player>,
+    /// Who won the previous round, if anyone, and what its secret was.
+    last: Option<(Option<String>, u64)>,
+}
+
+struct Player {
+    game: Game,
+    /// Clients currently playing under this name.
+    connections: usize,
+}
+
+impl Server {
+    pub fn bind(
+        addr: impl ToSocketAddrs,
+        config: ServerConfig,
+        secrets: impl SecretSource + Send + 'static,
+    ) -> io::Result<Self> {
+        assert!(
+            !config.range.is_empty(),
+            "the guessing range must not be empty"
+        );
+
+        let listener = TcpListener::bind(addr)?;
+        let mut secrets: Box<dyn SecretSource + Send> = Box::new(secrets);
+        let race = Race {
+            round: 0,
+            secret: secrets.pick(&config.range),
+            players: HashMap::new(),
+            last: None,
+        };
+
+        Ok(Self {
+            listener,
+            shared: Arc::new(Shared {
+                config,
+                secrets: Mutex::new(secrets),
+                race: Mutex::new(race),
+                clients: AtomicUsize::new(0),
+            }),
+        })
+    }
+
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Serves clients forever, each on its own thread.
+    pub fn run(self) -> io::Result<()> {
+        for stream in self.listener.incoming() {
+            let mut stream = match stream {
+                Ok(stream) => stream,
+                Err(err) => {
+                    eprintln!("failed to accept connection: {err}");
+                    continue;
+                }
+            };
+
+            let Some(slot) = Slot::claim(&self.shared) else {
+                // Best effort, the client may already be gone.
+                let _ = send_line(&mut stream, "ERR server full");
+                continue;
+            };
+            thread::spawn(move || {
+                let peer = stream
+                    .peer_addr()
+                    .map_or_else(|_| "unknown".to_string(), |addr| addr.to_string());
+                // Disconnects mid-game are routine, the other clients carry on.
+                if let Err(err) = serve_client(&slot.0, &stream, peer.clone()) {
+                    eprintln!("client {peer} dropped: {err}");
+                }
+                // Free the place before hanging up, so a client that
+                // reconnects as soon as it sees the close gets in.
+                drop(slot);
+            });
+        }
+        Ok(())
+    }
+}
+
+/// One of the `max_clients` places, given back when the client's thread ends.
+struct Slot(Arc<Shared>);
+
+impl Slot {
+    fn claim(shared: &Arc<Shared>) -> Option<Self> {
+        shared
+            .clients
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |clients| {
+                (clients < shared.config.max_clients).then_some(clients + 1)
+            })
+            .ok()
+            .map(|_| Self(Arc::clone(shared)))
+    }
+}
+
+impl Drop for Slot {
+    fn drop(&mut self) {
+        self.0.clients.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    // A client thread panicking mid-update can't leave a game in a state
+    // worse than the one it was already in, so carry on regardless.
+    mutex
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+struct Session {
+    name: String,
+    /// The client's own game; in a race it's kept in `Race::players`.
+    game: Option<Game>,
+    round: u64,
+}
+
+impl Shared {
+    fn new_game(&self, secret_source: impl SecretSource) -> Game {
+        let game = Game::new(self.config.range.clone(), secret_source);
+        match self.config.max_attempts {
+            Some(max) => game.with_max_attempts(max),
+            None => game,
+        }
+    }
+
+    /// Starts a session, joining the current round under `name` in a race.
+    fn join(&self, name: String) -> Session {
+        if !self.config.race {
+            let game = self.new_game(&mut **lock(&self.secrets));
+            return Session {
+                name,
+                game: Some(game),
+                round: 0,
+            };
+        }
+
+        let mut race = lock(&self.race);
+        let secret = race.secret;
+        let player = race.players.entry(name.clone()).or_insert_with(|| Player {
+            game: self.new_game(FixedSecret(secret)),
+            connections: 0,
+        });
+        player.connections += 1;
+        Session {
+            name,
+            game: None,
+            round: race.round,
+        }
+    }
+
+    /// Ends a session, which may leave nobody in the race who can still win.
+    fn leave(&self, session: &Session) {
+        if !self.config.race {
+            return;
+        }
+
+        let mut race = lock(&self.race);
+        if race.round != session.round {
+            return;
+        }
+        if let Some(player) = race.players.get_mut(&session.name) {
+            player.connections -= 1;
+            if player.connections == 0 && player.game.attempts() == 0 {
+                race.players.remove(&session.name);
+            }
+        }
+        self.end_if_stuck(&mut race);
+    }
+
+    fn end_round(&self, race: &mut Race, winner: Option<String>) {
+        race.last = Some((winner, race.secret));
+        race.round += 1;
+        race.secret = lock(&self.secrets).pick(&self.config.range);
+        race.players.clear();
+    }
+
+    /// Ends the round without a winner once somebody ran out of attempts and
+    /// nobody still connected has any left.
+    fn end_if_stuck(&self, race: &mut Race) {
+        let players = || race.players.values();
+        let stuck = players().any(|player| player.game.is_lost())
+            && players()
+                .filter(|player| player.connections > 0)
+                .all(|player| player.game.is_lost());
+        if stuck {
+            self.end_round(race, None);
+        }
+    }
+
+    fn hello(&self) -> String {
+        let range = &self.config.range;
+        match self.config.max_attempts {
+            Some(max) => format!("HELLO {} {} {max}", range.start(), range.end()),
+            None => format!("HELLO {} {}", range.start(), range.end()),
+        }
+    }
+
+    fn guess(&self, session: &mut Session, guess: u64) -> String {
+        if let Some(game) = &mut session.game {
+            let outcome = game.guess(guess);
+            return match outcome {
+                Outcome::GameOver => "ERR game over, send NEW to play again".to_string(),
+                _ => reply(game, outcome, true),
+            };
+        }
+
+        let mut race = lock(&self.race);
+        if race.round != session.round {
+            let (winner, secret) = race.last.clone().expect("a round has ended");
+            drop(race);
+            *session = self.join(session.name.clone());
+            let winner = winner.as_deref().unwrap_or("-");
+            return format!("OVER {winner} {secret}\n{}", self.hello());
+        }
+
+        let player = race
+            .players
+            .get_mut(&session.name)
+            .expect("sessions join the round they are in");
+        let outcome = player.game.guess(guess);
+        let response = match outcome {
+            Outcome::GameOver => "ERR out of attempts, wait for the next round".to_string(),
+            _ => reply(&player.game, outcome, false),
+        };
+        if outcome == Outcome::Correct {
+            self.end_round(&mut race, Some(session.name.clone()));
+        } else {
+            self.end_if_stuck(&mut race);
+        }
+        response
+    }
+
+    /// Renames a session, moving it to that name's game in a race.
+    fn rename(&self, session: &mut Session, name: String) {
+        // A session left behind by the last round rejoins on its next guess.
+        if self.config.race && lock(&self.race).round == session.round {
+            self.leave(session);
+            *session = self.join(name);
+        } else {
+            session.name = name;
+        }
+    }
+}
+
+fn reply(game: &Game, outcome: Outcome, reveal_on_loss: bool) -> String {
+    match outcome {
+        Outcome::Correct => format!("WIN {}", game.attempts()),
+        _ if game.is_lost() && reveal_on_loss => format!("LOSE {}", game.secret()),
+        _ if game.is_lost() => "LOSE".to_string(),
+        Outcome::TooSmall => "LOW".to_string(),
+        Outcome::TooBig => "HIGH".to_string(),
+        Outcome::OutOfRange => "OUT".to_string(),
+        Outcome::RuledOut => "RULEDOUT".to_string(),
+        Outcome::GameOver => "ERR game over".to_string(),
+    }
+}
+
+/// Writes `line` and its newline in one go, so Nagle's algorithm doesn't hold
+/// the newline back waiting for an ACK.
+fn send_line(writer: &mut TcpStream, line: &str) -> io::Result<()> {
+    writer.write_all(format!("{line}\n").as_bytes())
+}
+
+fn serve_client(shared: &Shared, stream: &TcpStream, peer: String) -> io::Result<()> {
+    stream.set_read_timeout(shared.config.idle_timeout)?;
+    stream.set_write_timeout(shared.config.idle_timeout)?;
+    let reader = BufReader::new(stream.try_clone()?);
+    let writer = stream.try_clone()?;
+
+    let mut session = shared.join(peer);
+    let result = converse(shared, &mut session, reader, writer);
+    shared.leave(&session);
+    result
+}
+
+fn converse(
+    shared: &Shared,
+    session: &mut Session,
+    mut reader: BufReader<TcpStream>,
+    mut writer: TcpStream,
+) -> io::Result<()> {
+    send_line(&mut writer, &shared.hello())?;
+
+    loop {
+        let mut line = String::new();
+        match reader.read_line(&mut line) {
+            Ok(0) => return Ok(()),
+            Ok(_) => {}
+            // Unix reports an expired read timeout as `WouldBlock`, Windows
+            // as `TimedOut`.
+            Err(err)
+                if matches!(
+                    err.kind(),
+                    io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+                ) =>
+            {
+                return send_line(&mut writer, "ERR idle timeout");
+            }
+            Err(err) => return Err(err),
+        }
+
+        let mut words = line.split_whitespace();
+        let command = words.next().unwrap_or_default().to_ascii_uppercase();
+        let argument = words.next();
+
+        let response = match (command.as_str(), argument) {
+            ("GUESS", Some(n)) => match n.parse() {
+                Ok(guess) => shared.guess(session, guess),
+                Err(_) => format!("ERR '{n}' is not a number"),
+            },
+            ("NAME", Some(name)) => {
+                shared.rename(session, name.to_string());
+                "OK".to_string()
+            }
+            ("NEW", None) if shared.config.race => "ERR races can't be restarted".to_string(),
+            ("NEW", None) => {
+                *session = shared.join(session.name.clone());
+                shared.hello()
+            }
+            ("QUIT", None) if shared.config.race => {
+                send_line(&mut writer, "BYE")?;
+                return Ok(());
+            }
+            ("QUIT", None) => {
+                let secret = session.game.as_ref().map(Game::secret).unwrap_or_default();
+                send_line(&mut writer, &format!("BYE {secret}"))?;
+                return Ok(());
+            }
+            ("", _) => continue,
+            _ => format!("ERR unknown command '{}'", line.trim()),
+        };
+        send_line(&mut writer, &response)?;
+    }
+}
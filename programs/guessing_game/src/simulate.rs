@@ -0,0 +1,161 @@
+use serde::Serialize;
+
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+use std::ops::RangeInclusive;
+
+use crate::Game;
+use crate::distribution::{CumulativeWeights, DistributedSecret, Distribution};
+use crate::i18n::Messages;
+use crate::solver::{self, InconsistentFeedback, Strategy};
+
+/// What to simulate. Every strategy plays against the same seeded secrets.
+#[derive(Debug, Clone)]
+pub struct Simulation {
+    pub range: RangeInclusive<u64>,
+    pub max_attempts: Option<u32>,
+    pub games: u64,
+    pub seed: u64,
+    /// How secrets are drawn, known to the bots that can use it.
+    pub distribution: Distribution,
+}
+
+/// Attempt statistics for one strategy over many games.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Stats {
+    pub strategy: String,
+    pub games: u64,
+    pub wins: u64,
+    pub mean: f64,
+    pub median: f64,
+    pub worst: u32,
+    /// Number of games that took each attempt count.
+    pub histogram: BTreeMap<u32, u64>,
+}
+
+impl Simulation {
+    pub fn run(&self, strategy: Strategy) -> Result<Stats, InconsistentFeedback> {
+        let mut secrets = DistributedSecret::new(self.distribution.clone(), self.seed);
+        let weights = CumulativeWeights::new(&self.range, &self.distribution);
+        let mut histogram = BTreeMap::new();
+        let mut wins = 0;
+
+        for _ in 0..self.games {
+            let mut game = Game::new(self.range.clone(), &mut secrets);
+            if let Some(max_attempts) = self.max_attempts {
+                game = game.with_max_attempts(max_attempts);
+            }
+
+            let mut bot = strategy.solver_with(
+                self.range.clone(),
+                game.seed().unwrap_or_default(),
+                &weights,
+            );
+            solver::solve(&mut game, bot.as_mut())?;
+
+            if game.is_won() {
+                wins += 1;
+            }
+            *histogram.entry(game.attempts()).or_insert(0) += 1;
+        }
+
+        Ok(Stats::new(strategy, wins, histogram))
+    }
+}
+
+impl Stats {
+    fn new(strategy: Strategy, wins: u64, histogram: BTreeMap<u32, u64>) -> Self {
+        let games = histogram.values().sum::<u64>();
+        let mean = if games == 0 {
+            0.0
+        } else {
+            let total = histogram
+                .iter()
+                .map(|(&a, &count)| f64::from(a) * count as f64)
+                .sum::<f64>();
+            total / games as f64
+        };
+        // The `n`th game's attempts, counting from zero in attempt order.
+        let nth = |n: u64| {
+            let mut seen = 0;
+            histogram
+                .iter()
+                .find(|&(_, &count)| {
+                    seen += count;
+                    seen > n
+                })
+                .map_or(0.0, |(&a, _)| f64::from(a))
+        };
+        let median = match games {
+            0 => 0.0,
+            games if games % 2 == 1 => nth(games / 2),
+            games => (nth(games / 2 - 1) + nth(games / 2)) / 2.0,
+        };
+
+        Self {
+            strategy: strategy.to_string(),
+            games,
+            wins,
+            mean,
+            median,
+            worst: histogram.keys().next_back().copied().unwrap_or(0),
+            histogram,
+        }
+    }
+}
+
+/// Human-readable summary table followed by a histogram per strategy.
+pub fn write_table(out: &mut impl Write, stats: &[Stats], messages: &Messages) -> io::Result<()> {
+    writeln!(
+        out,
+        "{:<10} {:>8} {:>8} {:>8} {:>8} {:>6}",
+        messages.text("simulate_strategy"),
+        messages.text("simulate_games"),
+        messages.text("simulate_wins"),
+        messages.text("simulate_mean"),
+        messages.text("simulate_median"),
+        messages.text("simulate_worst"),
+    )?;
+    for s in stats {
+        writeln!(
+            out,
+            "{:<10} {:>8} {:>8} {:>8.2} {:>8.1} {:>6}",
+            s.strategy, s.games, s.wins, s.mean, s.median, s.worst
+        )?;
+    }
+
+    for s in stats {
+        writeln!(out)?;
+        let heading = messages.format("simulate_histogram", &[("strategy", &s.strategy)]);
+        writeln!(out, "{heading}")?;
+
+        let tallest = s.histogram.values().copied().max().unwrap_or(0);
+        for (attempts, &count) in &s.histogram {
+            let bar = "#".repeat((count * 40).div_ceil(tallest.max(1)) as usize);
+            writeln!(out, "{attempts:>6} {count:>8} {bar}")?;
+        }
+    }
+
+    Ok(())
+}
+
+/// One row per histogram bucket, with the summary repeated on each row so the
+/// file plots without any reshaping.
+pub fn write_csv(out: &mut impl Write, stats: &[Stats]) -> io::Result<()> {
+    writeln!(out, "strategy,games,wins,mean,median,worst,attempts,count")?;
+    for s in stats {
+        for (attempts, count) in &s.histogram {
+            writeln!(
+                out,
+                "{},{},{},{},{},{},{attempts},{count}",
+                s.strategy, s.games, s.wins, s.mean, s.median, s.worst
+            )?;
+        }
+    }
+    Ok(())
+}
+
+pub fn write_json(out: &mut impl Write, stats: &[Stats]) -> io::Result<()> {
+    serde_json::to_writer_pretty(&mut *out, stats)?;
+    writeln!(out)
+}
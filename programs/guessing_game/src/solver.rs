@@ -0,0 +1,274 @@
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+use crate::distribution::{CumulativeWeights, Distribution, EntropySolver};
+use crate::{Game, Outcome};
+
+/// A bot that plays the guessing game from the engine's feedback alone.
+pub trait Solver {
+    /// Next number to try, or `None` when the feedback so far rules out
+    /// every number in the range.
+    fn next_guess(&mut self) -> Option<u64>;
+
+    fn observe(&mut self, guess: u64, outcome: Outcome);
+}
+
+/// The numbers still consistent with every response seen so far.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Interval {
+    low: u64,
+    high: u64,
+    empty: bool,
+}
+
+impl Interval {
+    pub(crate) fn new(range: &RangeInclusive<u64>) -> Self {
+        Self {
+            low: *range.start(),
+            high: *range.end(),
+            empty: range.is_empty(),
+        }
+    }
+
+    pub(crate) fn get(&self) -> Option<RangeInclusive<u64>> {
+        (!self.empty).then_some(self.low..=self.high)
+    }
+
+    pub(crate) fn narrow(&mut self, guess: u64, outcome: Outcome) {
+        if self.empty {
+            return;
+        }
+
+        match outcome {
+            Outcome::TooSmall if guess >= self.high => self.empty = true,
+            Outcome::TooSmall => self.low = self.low.max(guess + 1),
+            Outcome::TooBig if guess <= self.low => self.empty = true,
+            Outcome::TooBig => self.high = self.high.min(guess - 1),
+            Outcome::Correct if (self.low..=self.high).contains(&guess) => {
+                self.low = guess;
+                self.high = guess;
+            }
+            Outcome::Correct => self.empty = true,
+            Outcome::OutOfRange | Outcome::RuledOut | Outcome::GameOver => {}
+        }
+
+        if self.low > self.high {
+            self.empty = true;
+        }
+    }
+}
+
+/// Always guesses the middle of the remaining interval.
+///
+/// Wins within ⌈log2(n + 1)⌉ attempts for a range of `n` numbers, which is
+/// ⌈log2(n)⌉ unless `n` is a power of two.
+pub struct BinarySearch {
+    interval: Interval,
+}
+
+impl BinarySearch {
+    pub fn new(range: RangeInclusive<u64>) -> Self {
+        Self {
+            interval: Interval::new(&range),
+        }
+    }
+
+    /// Worst-case attempts needed for a range of `len` numbers.
+    pub fn worst_case(len: u64) -> u32 {
+        // ⌈log2(len + 1)⌉ is the bit length of `len`.
+        u64::BITS - len.leading_zeros()
+    }
+}
+
+impl Solver for BinarySearch {
+    fn next_guess(&mut self) -> Option<u64> {
+        let range = self.interval.get()?;
+        let (low, high) = range.into_inner();
+        Some(low + (high - low) / 2)
+    }
+
+    fn observe(&mut self, guess: u64, outcome: Outcome) {
+        self.interval.narrow(guess, outcome);
+    }
+}
+
+/// Guesses a random number from the remaining interval.
+pub struct RandomGuess {
+    interval: Interval,
+    rng: ChaCha8Rng,
+}
+
+impl RandomGuess {
+    pub fn new(range: RangeInclusive<u64>, seed: u64) -> Self {
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        // `SeededSecret` draws from stream 0. Staying off it means a bot
+        // given the game's own seed can't just reproduce the secret.
+        rng.set_stream(1);
+
+        Self {
+            interval: Interval::new(&range),
+            rng,
+        }
+    }
+}
+
+impl Solver for RandomGuess {
+    fn next_guess(&mut self) -> Option<u64> {
+        let range = self.interval.get()?;
+        Some(self.rng.random_range(range))
+    }
+
+    fn observe(&mut self, guess: u64, outcome: Outcome) {
+        self.interval.narrow(guess, outcome);
+    }
+}
+
+/// Counts up from the bottom of the range one number at a time.
+pub struct Linear {
+    interval: Interval,
+}
+
+impl Linear {
+    pub fn new(range: RangeInclusive<u64>) -> Self {
+        Self {
+            interval: Interval::new(&range),
+        }
+    }
+}
+
+impl Solver for Linear {
+    fn next_guess(&mut self) -> Option<u64> {
+        self.interval.get().map(|range| *range.start())
+    }
+
+    fn observe(&mut self, guess: u64, outcome: Outcome) {
+        self.interval.narrow(guess, outcome);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Strategy {
+    Binary,
+    Random,
+    Linear,
+    /// Knows the secret's distribution and asks the most informative
+    /// question, see [`EntropySolver`].
+    Entropy,
+}
+
+impl Strategy {
+    pub const ALL: [Strategy; 4] = [
+        Strategy::Binary,
+        Strategy::Random,
+        Strategy::Linear,
+        Strategy::Entropy,
+    ];
+
+    /// A bot for secrets picked uniformly from `range`.
+    pub fn solver(self, range: RangeInclusive<u64>, seed: u64) -> Box<dyn Solver> {
+        self.solver_for(range, seed, &Distribution::Uniform)
+    }
+
+    /// A bot for secrets drawn from `distribution`, which only the bots that
+    /// can use it look at. Panics unless `distribution.check(&range)` passes.
+    pub fn solver_for(
+        self,
+        range: RangeInclusive<u64>,
+        seed: u64,
+        distribution: &Distribution,
+    ) -> Box<dyn Solver> {
+        let weights = CumulativeWeights::new(&range, distribution);
+        self.solver_with(range, seed, &weights)
+    }
+
+    /// Like [`Strategy::solver_for`], with the distribution's weights already
+    /// added up for `range`, so many games can share them.
+    pub fn solver_with(
+        self,
+        range: RangeInclusive<u64>,
+        seed: u64,
+        weights: &CumulativeWeights,
+    ) -> Box<dyn Solver> {
+        match self {
+            Strategy::Binary => Box::new(BinarySearch::new(range)),
+            Strategy::Random => Box::new(RandomGuess::new(range, seed)),
+            Strategy::Linear => Box::new(Linear::new(range)),
+            Strategy::Entropy => Box::new(EntropySolver::with_weights(range, weights)),
+        }
+    }
+}
+
+impl fmt::Display for Strategy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Strategy::Binary => "binary",
+            Strategy::Random => "random",
+            Strategy::Linear => "linear",
+            Strategy::Entropy => "entropy",
+        };
+        f.write_str(name)
+    }
+}
+
+impl FromStr for Strategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "binary" => Ok(Strategy::Binary),
+            "random" => Ok(Strategy::Random),
+            "linear" => Ok(Strategy::Linear),
+            "entropy" => Ok(Strategy::Entropy),
+            _ => Err(format!(
+                "unknown strategy '{s}' (expected binary, random, linear or entropy)"
+            )),
+        }
+    }
+}
+
+/// One bot guess and the engine's response to it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Turn {
+    pub guess: u64,
+    pub outcome: Outcome,
+}
+
+/// The engine's answers contradicted each other, leaving no possible secret.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InconsistentFeedback {
+    pub transcript: Vec<Turn>,
+}
+
+impl fmt::Display for InconsistentFeedback {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "feedback ruled out every number after {} guesses",
+            self.transcript.len()
+        )
+    }
+}
+
+impl std::error::Error for InconsistentFeedback {}
+
+/// Lets `solver` play `game` until it is over and returns every turn taken.
+pub fn solve(game: &mut Game, solver: &mut dyn Solver) -> Result<Vec<Turn>, InconsistentFeedback> {
+    let mut transcript = Vec::new();
+
+    while !game.is_over() {
+        let Some(guess) = solver.next_guess() else {
+            return Err(InconsistentFeedback { transcript });
+        };
+
+        let outcome = game.guess(guess);
+        solver.observe(guess, outcome);
+
+        transcript.push(Turn { guess, outcome });
+    }
+
+    Ok(transcript)
+}
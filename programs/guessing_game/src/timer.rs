@@ -0,0 +1,165 @@
+//! Countdowns for timed games.
+
+use std::cell::Cell;
+use std::io::{self, BufRead};
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::Game;
+
+/// Where a countdown gets the current time from.
+pub trait Clock {
+    fn now(&self) -> Instant;
+}
+
+impl<C: Clock + ?Sized> Clock for &C {
+    fn now(&self) -> Instant {
+        (**self).now()
+    }
+}
+
+/// The real time.
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// Time that only moves when told to. Handy for tests.
+pub struct ManualClock {
+    now: Cell<Instant>,
+}
+
+impl ManualClock {
+    pub fn new() -> Self {
+        Self {
+            now: Cell::new(Instant::now()),
+        }
+    }
+
+    pub fn advance(&self, by: Duration) {
+        self.now.set(self.now.get() + by);
+    }
+}
+
+impl Default for ManualClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Instant {
+        self.now.get()
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TimeLimits {
+    /// Time allowed for each guess before it is forfeited.
+    pub per_guess: Option<Duration>,
+    /// Time allowed for the whole game before it is lost.
+    pub per_game: Option<Duration>,
+}
+
+/// Which limit ran out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Expiry {
+    Guess,
+    Game,
+}
+
+/// Tracks the time limits of one game.
+pub struct Countdown<C: Clock = SystemClock> {
+    clock: C,
+    limits: TimeLimits,
+    game_started: Instant,
+    turn_started: Instant,
+}
+
+impl<C: Clock> Countdown<C> {
+    /// Starts both the game and the first turn now.
+    pub fn new(limits: TimeLimits, clock: C) -> Self {
+        let now = clock.now();
+        Self {
+            clock,
+            limits,
+            game_started: now,
+            turn_started: now,
+        }
+    }
+
+    pub fn limits(&self) -> TimeLimits {
+        self.limits
+    }
+
+    /// Restarts the per-guess countdown.
+    pub fn start_turn(&mut self) {
+        self.turn_started = self.clock.now();
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.clock.now() - self.game_started
+    }
+
+    /// Time left until the nearest deadline, `None` if nothing is limited.
+    pub fn remaining(&self) -> Option<Duration> {
+        self.left().map(|(_, left)| left).min()
+    }
+
+    /// The limit that has run out, preferring the whole game's.
+    pub fn expired(&self) -> Option<Expiry> {
+        self.left()
+            .filter(|(_, left)| left.is_zero())
+            .map(|(expiry, _)| expiry)
+            .max_by_key(|&expiry| expiry == Expiry::Game)
+    }
+
+    /// Applies whatever ran out to `game`. An expired guess forfeits one
+    /// attempt and starts the next turn; an expired game is lost.
+    pub fn enforce<T: Ord + Clone>(&mut self, game: &mut Game<T>) -> Option<Expiry> {
+        let expiry = self.expired()?;
+        match expiry {
+            Expiry::Guess => {
+                game.forfeit();
+                self.start_turn();
+            }
+            Expiry::Game => game.expire(),
+        }
+        Some(expiry)
+    }
+
+    /// Time left on each limit. Counted down from the limit rather than
+    /// up to a deadline, as limits can be too long for an `Instant` to hold.
+    fn left(&self) -> impl Iterator<Item = (Expiry, Duration)> {
+        let now = self.clock.now();
+        let guess = self
+            .limits
+            .per_guess
+            .map(|limit| (Expiry::Guess, limit.saturating_sub(now - self.turn_started)));
+        let game = self
+            .limits
+            .per_game
+            .map(|limit| (Expiry::Game, limit.saturating_sub(now - self.game_started)));
+        guess.into_iter().chain(game)
+    }
+}
+
+/// Reads `input` line by line on a background thread, so a caller can wait
+/// for the next line with `recv_timeout` instead of polling. The channel
+/// closes at end of input.
+pub fn spawn_lines(input: impl BufRead + Send + 'static) -> Receiver<io::Result<String>> {
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        for line in input.lines() {
+            let failed = line.is_err();
+            if sender.send(line).is_err() || failed {
+                return;
+            }
+        }
+    });
+    receiver
+}
@@ -0,0 +1,197 @@
+//! Recorded games, one JSON object per line.
+//!
+//! The first line is a `start` record with everything needed to set the game
+//! up again; each later `input` record holds one line the player typed, what
+//! it was taken to mean and the lines printed in answer:
+//!
+//! ```text
+//! {"type":"start","version":1,"seed":7,"min":1,"max":100,"max_attempts":null,"hints":[],"free_repeats":false,"lang":"en"}
+//! {"type":"input","line":"50","parsed":"guess 50","response":["Too big!"]}
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+use std::io::{self, BufRead, Write};
+
+use crate::console::{self, Guess, Response};
+use crate::hints::HintMode;
+use crate::i18n::{Locale, Messages};
+use crate::{Game, SeededSecret};
+
+/// Bumped whenever a change to the format would stop old files replaying.
+pub const VERSION: u32 = 1;
+
+/// The rules a recorded game was played under.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Setup {
+    pub version: u32,
+    pub seed: u64,
+    pub min: u64,
+    pub max: u64,
+    pub max_attempts: Option<u32>,
+    pub hints: Vec<HintMode>,
+    pub free_repeats: bool,
+    /// The language responses were recorded in.
+    #[serde(default)]
+    pub lang: Locale,
+}
+
+impl Setup {
+    /// `None` if `game` wasn't seeded, as its secret can't be recreated.
+    pub fn of(game: &Game, lang: Locale) -> Option<Setup> {
+        Some(Setup {
+            version: VERSION,
+            seed: game.seed()?,
+            min: *game.range().start(),
+            max: *game.range().end(),
+            max_attempts: game.max_attempts(),
+            hints: game.hint_modes().to_vec(),
+            free_repeats: game.free_repeats(),
+            lang,
+        })
+    }
+
+    /// A fresh game identical to the recorded one before its first input.
+    pub fn game(&self) -> Game {
+        let game = Game::new(self.min..=self.max, SeededSecret::new(self.seed))
+            .with_hints(self.hints.iter().copied())
+            .with_free_repeats(self.free_repeats);
+        match self.max_attempts {
+            Some(max) => game.with_max_attempts(max),
+            None => game,
+        }
+    }
+}
+
+/// One line the player typed and what the game said back.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Entry {
+    pub line: String,
+    pub parsed: String,
+    pub response: Vec<String>,
+}
+
+impl Entry {
+    pub fn new<T: Guess>(line: &str, response: &Response<T>) -> Entry {
+        Entry {
+            line: line.to_string(),
+            parsed: response.input.to_string(),
+            response: response.lines.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum Record {
+    Start(Setup),
+    Input(Entry),
+}
+
+/// Writes a transcript as the game goes, flushing after every line so a
+/// crash or Ctrl-C still leaves everything up to that point on disk.
+pub struct Recorder<W: Write> {
+    out: W,
+}
+
+impl<W: Write> Recorder<W> {
+    pub fn new(mut out: W, setup: &Setup) -> io::Result<Self> {
+        write_record(&mut out, &Record::Start(setup.clone()))?;
+        Ok(Self { out })
+    }
+
+    pub fn record(&mut self, entry: &Entry) -> io::Result<()> {
+        write_record(&mut self.out, &Record::Input(entry.clone()))
+    }
+}
+
+fn write_record(out: &mut impl Write, record: &Record) -> io::Result<()> {
+    let mut line = serde_json::to_string(record)?;
+    line.push('\n');
+    out.write_all(line.as_bytes())?;
+    out.flush()
+}
+
+/// A whole recorded game.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Transcript {
+    pub setup: Setup,
+    pub entries: Vec<Entry>,
+}
+
+impl Transcript {
+    /// Reads a transcript, rejecting anything malformed with `InvalidData`
+    /// and the offending line number.
+    pub fn load(input: impl BufRead) -> io::Result<Transcript> {
+        let mut setup = None;
+        let mut entries = Vec::new();
+
+        for (index, line) in input.lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let invalid = |message: String| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("line {}: {message}", index + 1),
+                )
+            };
+
+            let record = serde_json::from_str(&line).map_err(|err| invalid(err.to_string()))?;
+            match (record, &setup) {
+                (Record::Start(start), None) if start.version == VERSION => setup = Some(start),
+                (Record::Start(start), None) => {
+                    return Err(invalid(format!(
+                        "transcript version {} is not supported (expected {VERSION})",
+                        start.version
+                    )));
+                }
+                (Record::Start(_), Some(_)) => return Err(invalid("second start record".into())),
+                (Record::Input(_), None) => return Err(invalid("input before start".into())),
+                (Record::Input(entry), Some(_)) => entries.push(entry),
+            }
+        }
+
+        let setup = setup.ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidData, "transcript has no start record")
+        })?;
+        if setup.min > setup.max {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "transcript has an empty range",
+            ));
+        }
+        Ok(Transcript { setup, entries })
+    }
+
+    /// Feeds every recorded input to a fresh game in order, returning what
+    /// the engine answers now next to what was recorded.
+    pub fn replay(&self) -> Vec<Step<'_>> {
+        let mut game = self.setup.game();
+        let messages = Messages::new(self.setup.lang);
+        self.entries
+            .iter()
+            .map(|recorded| {
+                let response = console::respond(&mut game, &recorded.line, &messages);
+                Step {
+                    recorded,
+                    replayed: Entry::new(&recorded.line, &response),
+                }
+            })
+            .collect()
+    }
+}
+
+/// One input of a replay.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Step<'a> {
+    pub recorded: &'a Entry,
+    pub replayed: Entry,
+}
+
+impl Step<'_> {
+    pub fn matches(&self) -> bool {
+        *self.recorded == self.replayed
+    }
+}
@@ -0,0 +1,363 @@
+//! Full-screen terminal front-end, enabled by the `tui` feature.
+
+use crossterm::cursor::{Hide, MoveTo, Show};
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::style::{Color, Print, ResetColor, SetForegroundColor};
+use crossterm::terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen};
+use crossterm::{execute, queue};
+
+use std::io::{self, Write};
+use std::ops::RangeInclusive;
+use std::time::{Duration, Instant};
+
+use crate::console;
+use crate::i18n::Messages;
+use crate::{Game, Outcome};
+
+/// How often the screen is redrawn while waiting for a key, to keep the
+/// timer moving.
+const TICK: Duration = Duration::from_millis(200);
+
+/// Puts the terminal back the way it was, even if drawing fails halfway.
+struct RawScreen;
+
+impl RawScreen {
+    fn enter(out: &mut impl Write) -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        execute!(out, EnterAlternateScreen, Hide)?;
+        Ok(Self)
+    }
+}
+
+impl Drop for RawScreen {
+    fn drop(&mut self) {
+        let _ = execute!(io::stdout(), Show, LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+/// The game as shown full screen: the guess being edited, past answers and
+/// the latest message. Key presses go through [`Screen::handle_key`].
+pub struct Screen<'a> {
+    game: &'a mut Game,
+    started: Instant,
+    /// The guess being edited, as typed or nudged.
+    entry: String,
+    /// Newest first.
+    history: Vec<String>,
+    message: String,
+    messages: &'a Messages,
+}
+
+/// Plays `game` full screen until it is won, lost or the player quits.
+pub fn run(game: &mut Game, messages: &Messages) -> io::Result<()> {
+    let mut out = io::stdout();
+    let _raw = RawScreen::enter(&mut out)?;
+
+    let mut screen = Screen::new(game, messages);
+
+    loop {
+        screen.draw(&mut out)?;
+
+        if !event::poll(TICK)? {
+            continue;
+        }
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+
+        if screen.game.is_over() {
+            return Ok(());
+        }
+        if !screen.handle_key(key) {
+            return Ok(());
+        }
+    }
+}
+
+/// Leaves raw mode when a hidden read ends, even on error.
+struct RawMode;
+
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+/// Reads a line from the terminal without echoing it, for secrets typed at
+/// the hot-seat prompt. Returns 0 on Ctrl-C or Ctrl-D at an empty line, like
+/// end of input.
+pub fn read_hidden_line(line: &mut String) -> io::Result<usize> {
+    terminal::enable_raw_mode()?;
+    let _raw = RawMode;
+
+    let start = line.len();
+    loop {
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        let control = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Enter => {
+                line.push('\n');
+                return Ok(line.len() - start);
+            }
+            KeyCode::Char('c') if control => return Ok(0),
+            KeyCode::Char('d') if control && line.len() == start => return Ok(0),
+            KeyCode::Backspace if line.len() > start => {
+                line.pop();
+            }
+            KeyCode::Char(c) if !control => line.push(c),
+            _ => {}
+        }
+    }
+}
+
+impl<'a> Screen<'a> {
+    /// Starts with the middle of the range entered.
+    pub fn new(game: &'a mut Game, messages: &'a Messages) -> Self {
+        let middle = {
+            let interval = game.interval();
+            interval.start() + (interval.end() - interval.start()) / 2
+        };
+        Self {
+            game,
+            started: Instant::now(),
+            entry: middle.to_string(),
+            history: Vec::new(),
+            message: messages.text("tui_start").to_string(),
+            messages,
+        }
+    }
+
+    /// The guess being edited.
+    pub fn entry(&self) -> &str {
+        &self.entry
+    }
+
+    /// Answers so far, newest first.
+    pub fn history(&self) -> &[String] {
+        &self.history
+    }
+
+    /// The line under the entry: a result, an error or nothing.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Applies one key press. Returns `false` when the player quits.
+    pub fn handle_key(&mut self, key: KeyEvent) -> bool {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => return false,
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => return false,
+            KeyCode::Char(digit @ '0'..='9') => self.entry.push(digit),
+            KeyCode::Backspace => {
+                self.entry.pop();
+            }
+            KeyCode::Left => self.nudge(-1),
+            KeyCode::Right => self.nudge(1),
+            KeyCode::Down => self.nudge(-10),
+            KeyCode::Up => self.nudge(10),
+            KeyCode::Enter => self.submit(),
+            _ => {}
+        }
+        true
+    }
+
+    /// Moves the entry by `delta`, staying inside the range. Typing may still
+    /// leave it, which the engine reports on Enter.
+    fn nudge(&mut self, delta: i64) {
+        let (min, max) = self.game.range().clone().into_inner();
+        let current = self.entry.parse::<u64>().unwrap_or(min);
+        let moved = current.saturating_add_signed(delta);
+        self.entry = moved.clamp(min, max).to_string();
+    }
+
+    fn submit(&mut self) {
+        let Ok(guess) = self.entry.parse::<u64>() else {
+            self.message = self.messages.text("invalid_number").to_string();
+            return;
+        };
+
+        let outcome = self.game.guess(guess);
+        let mut line = format!("{guess:>8}  {}", self.describe(outcome));
+        if matches!(outcome, Outcome::TooSmall | Outcome::TooBig) {
+            for hint in self.game.hints() {
+                line.push_str(&format!("  {}", console::hint(hint, self.messages)));
+            }
+        }
+        self.history.insert(0, line);
+
+        self.message = if self.game.is_won() {
+            let attempts = self.game.attempts();
+            self.messages.format("tui_won", &[("attempts", &attempts)])
+        } else if self.game.is_lost() {
+            let secret = self.game.secret();
+            self.messages.format("tui_lost", &[("secret", &secret)])
+        } else {
+            String::new()
+        };
+
+        // Start the next guess from the middle of what's left.
+        let interval = self.game.interval();
+        self.entry = (interval.start() + (interval.end() - interval.start()) / 2).to_string();
+    }
+
+    fn draw(&self, out: &mut impl Write) -> io::Result<()> {
+        let (width, height) = terminal::size()?;
+        let width = width.max(20);
+        queue!(out, Clear(ClearType::All), MoveTo(0, 0))?;
+
+        let range = self.game.range();
+        let remaining = match self.game.remaining_attempts() {
+            Some(left) => self
+                .messages
+                .format("tui_attempts_left", &[("left", &left)]),
+            None => {
+                let attempts = self.game.attempts();
+                self.messages
+                    .format("tui_attempts", &[("attempts", &attempts)])
+            }
+        };
+        let seconds = format!("{:>5.1}", self.started.elapsed().as_secs_f64());
+        let title = self.messages.format(
+            "tui_title",
+            &[
+                ("min", range.start()),
+                ("max", range.end()),
+                ("attempts", &remaining),
+                ("seconds", &seconds),
+            ],
+        );
+        queue!(out, Print(title))?;
+
+        self.draw_number_line(out, width - 4)?;
+
+        queue!(
+            out,
+            MoveTo(2, 6),
+            Print(format!("{} ", self.messages.text("tui_guess"))),
+            SetForegroundColor(Color::Yellow),
+            Print(&self.entry),
+            Print("_"),
+            ResetColor,
+            MoveTo(2, 7),
+            Print(&self.message),
+            MoveTo(2, 9),
+            Print(self.messages.text("tui_history")),
+        )?;
+
+        let rows = height.saturating_sub(12) as usize;
+        for (row, line) in self.history.iter().take(rows).enumerate() {
+            queue!(out, MoveTo(2, 10 + row as u16), Print(line))?;
+        }
+
+        queue!(
+            out,
+            MoveTo(2, height.saturating_sub(1)),
+            SetForegroundColor(Color::DarkGrey),
+            Print(self.messages.text("tui_keys")),
+            ResetColor,
+        )?;
+        out.flush()
+    }
+
+    fn describe(&self, outcome: Outcome) -> &'static str {
+        let key = match outcome {
+            Outcome::TooSmall => "too_small",
+            Outcome::TooBig => "too_big",
+            Outcome::Correct => "you_win",
+            Outcome::OutOfRange => "tui_out_of_range",
+            Outcome::RuledOut => "tui_ruled_out",
+            Outcome::GameOver => "game_over",
+        };
+        self.messages.text(key)
+    }
+
+    /// One cell per slice of the range: grey where the secret has been ruled
+    /// out, green where it may still be, with a marker under the entry.
+    fn draw_number_line(&self, out: &mut impl Write, cells: u16) -> io::Result<()> {
+        let range = self.game.range();
+        let line = NumberLine::new(range, cells);
+        let interval = self.game.interval();
+
+        queue!(out, MoveTo(2, 2))?;
+        for cell in 0..line.cells() {
+            let covers = line.covers(cell);
+            let open = covers.start().max(interval.start()) <= covers.end().min(interval.end());
+
+            let color = if open { Color::Green } else { Color::DarkGrey };
+            queue!(
+                out,
+                SetForegroundColor(color),
+                Print(if open { '█' } else { '░' })
+            )?;
+        }
+        queue!(out, ResetColor)?;
+
+        if let Ok(entry) = self.entry.parse::<u64>()
+            && range.contains(&entry)
+        {
+            queue!(out, MoveTo(2 + line.cell_of(entry), 3), Print('^'))?;
+        }
+        let max = range.end().to_string();
+        queue!(
+            out,
+            MoveTo(2, 4),
+            Print(range.start()),
+            MoveTo((2 + line.cells()).saturating_sub(max.len() as u16), 4),
+            Print(max),
+        )
+    }
+}
+
+/// How the guessing range is split into the cells of the number line.
+#[derive(Debug, Clone)]
+pub struct NumberLine {
+    min: u64,
+    max: u64,
+    cells: u16,
+}
+
+impl NumberLine {
+    /// Splits `range` into `width` cells, or one per number if there are
+    /// fewer numbers than that.
+    pub fn new(range: &RangeInclusive<u64>, width: u16) -> Self {
+        let (min, max) = range.clone().into_inner();
+        let span = u128::from(max - min) + 1;
+        Self {
+            min,
+            max,
+            cells: width.min(u16::try_from(span).unwrap_or(u16::MAX)),
+        }
+    }
+
+    pub fn cells(&self) -> u16 {
+        self.cells
+    }
+
+    /// The cell `n` falls in. `n` must be inside the range.
+    pub fn cell_of(&self, n: u64) -> u16 {
+        let cell = u128::from(n - self.min) * u128::from(self.cells) / self.span();
+        cell as u16
+    }
+
+    /// The numbers cell `cell` stands for.
+    pub fn covers(&self, cell: u16) -> RangeInclusive<u64> {
+        // In u128 because the full u64 range has 2^64 numbers.
+        let edge = |cell: u128| u128::from(self.min) + cell * self.span() / u128::from(self.cells);
+        let first = edge(cell.into());
+        let last = (edge(u128::from(cell) + 1) - 1).min(u128::from(self.max));
+        first as u64..=last as u64
+    }
+
+    fn span(&self) -> u128 {
+        u128::from(self.max - self.min) + 1
+    }
+}
@@ -0,0 +1,270 @@
+//! Ulam's searching game: the game may lie about a few of its "too small" and
+//! "too big" answers, and the player has to find the secret anyway.
+
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use std::ops::RangeInclusive;
+
+use crate::Outcome;
+use crate::solver::{BinarySearch, Solver};
+
+/// Decides which answers are lies, up to a fixed number of them.
+#[derive(Debug, Clone)]
+pub struct Liar {
+    max_lies: u32,
+    chance: f64,
+    rng: ChaCha8Rng,
+}
+
+impl Liar {
+    /// Lies about a quarter of the time until `max_lies` are used up.
+    pub fn new(max_lies: u32, seed: u64) -> Self {
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        // Streams 0 and 1 pick secrets and random guesses.
+        rng.set_stream(2);
+
+        Self {
+            max_lies,
+            chance: 0.25,
+            rng,
+        }
+    }
+
+    /// How likely each answer is to be a lie while any are left. `1.0`
+    /// spends every lie on the first answers.
+    pub fn with_chance(mut self, chance: f64) -> Self {
+        self.chance = chance.clamp(0.0, 1.0);
+        self
+    }
+
+    pub fn max_lies(&self) -> u32 {
+        self.max_lies
+    }
+
+    /// Whether to lie about the next answer, given `told` lies so far.
+    pub(crate) fn lies(&mut self, told: u32) -> bool {
+        told < self.max_lies && self.rng.random_bool(self.chance)
+    }
+}
+
+/// Most lies a game can tell. The solver's weights grow like `2^q` for `q`
+/// questions, and more lies than this would need more questions than an
+/// `f64` can weigh.
+pub const MAX_LIES: u32 = 100;
+
+/// Berlekamp's volume strategy for the liar game.
+///
+/// Every number still possible is weighed by how many answer sequences could
+/// still lead to it: with `q` questions to go and `l` lies left, that's
+/// `C(q, 0) + C(q, 1) + ... + C(q, l)`, one per way of placing the lies.
+/// The two answers to any guess share that weight between them, so each
+/// guess is picked to split it as evenly as the numbers allow, charging a
+/// lie to every number the answer contradicts. Numbers charged more than
+/// `max_lies` lies are dropped. See [`UlamSolver::worst_case`] for how many
+/// attempts that takes.
+#[derive(Debug, Clone)]
+pub struct UlamSolver {
+    max_lies: u32,
+    /// The numbers still possible in ascending order, as runs of neighbours
+    /// that imply the same number of lies.
+    runs: Vec<Run>,
+    /// Lies proven so far.
+    lies_found: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Run {
+    low: u64,
+    high: u64,
+    /// Lies the answers so far imply if the secret is in this run.
+    lies: u32,
+}
+
+impl Run {
+    fn len(&self) -> f64 {
+        (self.high - self.low) as f64 + 1.0
+    }
+}
+
+impl UlamSolver {
+    /// Panics if `max_lies` is more than [`MAX_LIES`].
+    pub fn new(range: RangeInclusive<u64>, max_lies: u32) -> Self {
+        assert!(max_lies <= MAX_LIES, "there can be at most {MAX_LIES} lies");
+        let runs = if range.is_empty() {
+            Vec::new()
+        } else {
+            vec![Run {
+                low: *range.start(),
+                high: *range.end(),
+                lies: 0,
+            }]
+        };
+        Self {
+            max_lies,
+            runs,
+            lies_found: 0,
+        }
+    }
+
+    /// Worst-case attempts this solver needs for a range of `len` numbers
+    /// when up to `max_lies` answers may be lies.
+    ///
+    /// That's Berlekamp's volume bound, the fewest `q` with
+    /// `len * (C(q, 0) + ... + C(q, max_lies)) <= 2^q`, which no strategy can
+    /// beat when every guess misses, or the lie-free worst case if that's
+    /// more. The bound is checked against every possible liar for small
+    /// ranges in the tests rather than proven.
+    pub fn worst_case(len: u64, max_lies: u32) -> u32 {
+        let volume = (0..)
+            .find(|&questions| {
+                len as f64 * weights(questions, max_lies)[0] <= 2f64.powi(questions as i32)
+            })
+            .expect("some number of questions always suffices");
+        volume.max(BinarySearch::worst_case(len))
+    }
+
+    /// Lies the answers so far prove were told.
+    pub fn lies_found(&self) -> u32 {
+        self.lies_found
+    }
+
+    /// The fewest questions whose volume covers every number still possible.
+    fn questions_left(&self) -> u32 {
+        (0..)
+            .find(|&questions| {
+                let weights = weights(questions, self.max_lies);
+                let total: f64 = self
+                    .runs
+                    .iter()
+                    .map(|run| run.len() * weights[run.lies as usize])
+                    .sum();
+                total <= 2f64.powi(questions as i32)
+            })
+            .expect("some number of questions always suffices")
+    }
+}
+
+/// The weight of a number by the lies it implies, with `questions`
+/// questions to go: `C(q, 0) + ... + C(q, max_lies - lies)`, and nothing for
+/// one more lie than allowed.
+fn weights(questions: u32, max_lies: u32) -> Vec<f64> {
+    let mut weights = vec![0.0; max_lies as usize + 2];
+    let (mut binomial, mut sum) = (1.0, 0.0);
+    for j in 0..=max_lies {
+        if j > 0 {
+            binomial = binomial * f64::from(questions.saturating_sub(j - 1)) / f64::from(j);
+        }
+        sum += binomial;
+        weights[(max_lies - j) as usize] = sum;
+    }
+    weights
+}
+
+impl Solver for UlamSolver {
+    fn next_guess(&mut self) -> Option<u64> {
+        if self.runs.is_empty() {
+            return None;
+        }
+
+        // Weigh the numbers as they'll stand after this question.
+        let weights = weights(self.questions_left().saturating_sub(1), self.max_lies);
+        let kept = |run: &Run| weights[run.lies as usize];
+        let caught = |run: &Run| weights[run.lies as usize + 1];
+
+        // Weight left by "too small" and by "too big" from the runs after
+        // each one. "Too small" is a lie for every number below the guess.
+        let mut after = vec![(0.0, 0.0); self.runs.len()];
+        for index in (1..self.runs.len()).rev() {
+            let run = &self.runs[index];
+            let (too_small, too_big) = after[index];
+            after[index - 1] = (
+                too_small + run.len() * kept(run),
+                too_big + run.len() * caught(run),
+            );
+        }
+
+        let mut before = (0.0, 0.0);
+        let mut best: Option<(u64, f64)> = None;
+        for (run, after) in self.runs.iter().zip(after) {
+            let (kept, caught) = (kept(run), caught(run));
+            let outside_small = before.0 + after.0;
+            let outside_big = before.1 + after.1;
+
+            // Guessing `steps_below` numbers into the run, both answers'
+            // weights are linear in it. Aim for where they cross.
+            let steps = run.high - run.low;
+            let crossing = if kept > caught {
+                (outside_small - outside_big) / (2.0 * (kept - caught)) + steps as f64 / 2.0
+            } else {
+                0.0
+            };
+            let crossing = crossing.clamp(0.0, steps as f64);
+
+            for steps_below in [crossing.floor(), crossing.ceil()] {
+                // Rounding to `f64` can overshoot the last step of a huge run.
+                let steps_below = (steps_below as u64).min(steps);
+                let steps_above = steps - steps_below;
+                let too_small =
+                    outside_small + steps_above as f64 * kept + steps_below as f64 * caught;
+                let too_big = outside_big + steps_below as f64 * kept + steps_above as f64 * caught;
+                let worst = too_small.max(too_big);
+                if best.is_none_or(|(_, least)| worst < least) {
+                    best = Some((run.low + steps_below, worst));
+                }
+            }
+
+            before.0 += run.len() * caught;
+            before.1 += run.len() * kept;
+        }
+        best.map(|(guess, _)| guess)
+    }
+
+    fn observe(&mut self, guess: u64, outcome: Outcome) {
+        // Numbers on the wrong side of the answer imply one more lie, and
+        // the guess itself is out as `Correct` is never a lie.
+        let (below, above) = match outcome {
+            Outcome::TooSmall => (1, 0),
+            Outcome::TooBig => (0, 1),
+            Outcome::Correct => {
+                let found = self
+                    .runs
+                    .iter()
+                    .find(|run| (run.low..=run.high).contains(&guess))
+                    .map(|run| Run {
+                        low: guess,
+                        high: guess,
+                        lies: run.lies,
+                    });
+                if let Some(run) = found {
+                    self.lies_found = run.lies;
+                }
+                self.runs = found.into_iter().collect();
+                return;
+            }
+            _ => return,
+        };
+
+        let mut runs: Vec<Run> = Vec::with_capacity(self.runs.len() + 1);
+        for run in self.runs.drain(..) {
+            let parts = [
+                (guess > run.low).then(|| (run.low, run.high.min(guess - 1), below)),
+                (guess < run.high).then(|| (run.low.max(guess + 1), run.high, above)),
+            ];
+            for (low, high, extra) in parts.into_iter().flatten() {
+                let lies = run.lies + extra;
+                if lies > self.max_lies {
+                    continue;
+                }
+                match runs.last_mut() {
+                    Some(last) if last.lies == lies && last.high + 1 == low => last.high = high,
+                    _ => runs.push(Run { low, high, lies }),
+                }
+            }
+        }
+        self.runs = runs;
+        if let Some(fewest) = self.runs.iter().map(|run| run.lies).min() {
+            self.lies_found = fewest;
+        }
+    }
+}
@@ -0,0 +1,45 @@
+//! The bundled word list for guessing words in dictionary order.
+
+use std::sync::OnceLock;
+
+/// Every word the secret can be, sorted and without duplicates.
+pub fn list() -> &'static [&'static str] {
+    static WORDS: OnceLock<Vec<&'static str>> = OnceLock::new();
+
+    WORDS.get_or_init(|| {
+        let mut words: Vec<&str> = include_str!("../words.txt")
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .collect();
+        words.sort_unstable();
+        words.dedup();
+        words
+    })
+}
+
+/// The listed word matching `word`, ignoring case and surrounding blanks.
+pub fn lookup(word: &str) -> Option<&'static str> {
+    let word = word.trim().to_lowercase();
+    let words = list();
+    words.binary_search(&word.as_str()).ok().map(|i| words[i])
+}
+
+/// Where `word` is in the list, if it's there.
+pub fn position(word: &str) -> Option<usize> {
+    list().binary_search(&word).ok()
+}
+
+/// The listed word right after `word`, if any.
+pub fn next(word: &str) -> Option<&'static str> {
+    let words = list();
+    let i = words.partition_point(|&listed| listed <= word);
+    words.get(i).copied()
+}
+
+/// The listed word right before `word`, if any.
+pub fn previous(word: &str) -> Option<&'static str> {
+    let words = list();
+    let i = words.partition_point(|&listed| listed < word);
+    i.checked_sub(1).map(|i| words[i])
+}
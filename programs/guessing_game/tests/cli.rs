@@ -0,0 +1,445 @@
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output, Stdio};
+
+fn run(args: &[&str], envs: &[(&str, &str)], stdin: &str) -> Output {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_guessing_game"));
+    command
+        .args(args)
+        .env_remove("GUESS_DEBUG")
+        .env_remove("LC_ALL")
+        .env_remove("LC_MESSAGES")
+        .env("LANG", "C")
+        .env("XDG_DATA_HOME", data_dir("shared"))
+        .envs(envs.iter().copied())
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+
+    let mut child = command.spawn().expect("failed to start guessing_game");
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(stdin.as_bytes())
+        .unwrap();
+    child.wait_with_output().unwrap()
+}
+
+/// A per-test data directory so score files don't leak into `$HOME`.
+fn data_dir(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_TARGET_TMPDIR")).join(name)
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+#[test]
+fn secret_is_hidden_by_default() {
+    let output = run(&["--min", "42", "--max", "42"], &[], "42\n");
+    let out = stdout(&output);
+
+    assert!(!out.contains("Secret number is"), "{out}");
+    assert!(out.contains("You win!"), "{out}");
+}
+
+#[test]
+fn reveal_flag_shows_secret() {
+    let output = run(&["--min", "42", "--max", "42", "--reveal"], &[], "42\n");
+
+    assert!(stdout(&output).starts_with("Secret number is: 42\n"));
+}
+
+#[test]
+fn reveal_works_in_hot_seat_games_without_a_chooser() {
+    let args = [
+        "--players",
+        "ann,bob",
+        "--min",
+        "7",
+        "--max",
+        "7",
+        "--reveal",
+    ];
+    let output = run(&args, &[], "7\n");
+    assert!(stdout(&output).starts_with("Secret number is: 7\n"));
+
+    let chosen = run(&[&args[..], &["--chooser", "ann"]].concat(), &[], "");
+    assert_eq!(chosen.status.code(), Some(2));
+}
+
+#[test]
+fn language_comes_from_lang_or_the_flag() {
+    let from_env = run(
+        &["--min", "42", "--max", "42"],
+        &[("LANG", "de_DE.UTF-8")],
+        "42\n",
+    );
+    let from_flag = run(&["--lang", "de", "--min", "42", "--max", "42"], &[], "42\n");
+    let overridden = run(
+        &["--lang", "en", "--min", "42", "--max", "42"],
+        &[("LANG", "de_DE.UTF-8")],
+        "42\n",
+    );
+
+    assert!(stdout(&from_env).starts_with("Willkommen beim Ratespiel!"));
+    assert!(stdout(&from_flag).starts_with("Willkommen beim Ratespiel!"));
+    assert!(stdout(&overridden).starts_with("Welcome to the Guessing Game!"));
+}
+
+#[test]
+fn guess_debug_env_shows_secret() {
+    let shown = run(
+        &["--min", "42", "--max", "42"],
+        &[("GUESS_DEBUG", "1")],
+        "42\n",
+    );
+    let hidden = run(
+        &["--min", "42", "--max", "42"],
+        &[("GUESS_DEBUG", "0")],
+        "42\n",
+    );
+
+    assert!(stdout(&shown).contains("Secret number is: 42"));
+    assert!(!stdout(&hidden).contains("Secret number is"));
+}
+
+#[test]
+fn secret_is_revealed_when_player_quits() {
+    let output = run(&["--min", "42", "--max", "42"], &[], "");
+    let out = stdout(&output);
+
+    assert!(!out.contains("Secret number is"), "{out}");
+    assert!(out.contains("The secret number was 42."), "{out}");
+}
+
+#[test]
+fn same_seed_replays_same_secret() {
+    let first = stdout(&run(&["--seed", "7", "--reveal"], &[], ""));
+    let second = stdout(&run(&["--seed", "7", "--reveal"], &[], ""));
+
+    assert!(first.starts_with("Secret number is: "), "{first}");
+    assert_eq!(first, second);
+    assert!(first.contains("Seed: 7 "), "{first}");
+}
+
+#[test]
+fn exit_codes_distinguish_win_quit_and_loss() {
+    let won = run(&["--min", "42", "--max", "42"], &[], "42\n");
+    let quit = run(&["--min", "42", "--max", "42"], &[], "abc\nquit\n");
+    let eof = run(&["--min", "42", "--max", "42"], &[], "abc\n");
+
+    assert_eq!(won.status.code(), Some(0));
+    assert_eq!(quit.status.code(), Some(3));
+    assert_eq!(eof.status.code(), Some(3));
+    assert!(stdout(&quit).contains("The secret number was 42."));
+
+    // Easy allows 10 attempts; keep guessing the one wrong number in 1..=2.
+    let revealed = stdout(&run(
+        &["-d", "easy", "--max", "2", "--seed", "1", "--reveal"],
+        &[],
+        "",
+    ));
+    let wrong = if revealed.starts_with("Secret number is: 1\n") {
+        "2\n"
+    } else {
+        "1\n"
+    };
+    let lost = run(
+        &["-d", "easy", "--max", "2", "--seed", "1"],
+        &[],
+        &wrong.repeat(10),
+    );
+
+    assert_eq!(lost.status.code(), Some(4));
+    assert!(stdout(&lost).contains("Out of attempts"));
+}
+
+#[test]
+fn difficulty_flag_picks_the_preset() {
+    let output = run(&["--difficulty", "hard", "--auto", "--seed", "1"], &[], "");
+    assert!(stdout(&output).contains("guessing from 1 to 1000"));
+
+    let short = run(&["-d", "easy", "--max", "30", "--auto"], &[], "");
+    assert!(stdout(&short).contains("guessing from 1 to 30"));
+
+    let unknown = run(&["-d", "impossible"], &[], "");
+    assert_eq!(unknown.status.code(), Some(2));
+}
+
+#[test]
+fn finished_games_are_listed_in_scores() {
+    let dir = data_dir("scores");
+    let _ = fs::remove_dir_all(&dir);
+    let xdg = dir.to_str().unwrap();
+
+    let env = [("XDG_DATA_HOME", xdg), ("GUESS_PLAYER", "ada")];
+    let easy: Vec<String> = (1..=10).map(|n| format!("{n}\n")).collect();
+    run(&["-d", "easy", "--seed", "1"], &env, &easy.concat());
+    run(&["-d", "easy", "--seed", "1"], &env, "quit\n");
+    run(&["-d", "easy", "--max", "3"], &env, "1\n2\n3\n");
+    run(&["-d", "easy", "--reveal"], &env, "quit\n");
+    let scores = stdout(&run(&["scores"], &[("XDG_DATA_HOME", xdg)], ""));
+
+    let recorded = fs::read_to_string(dir.join("guessing_game/scores.jsonl")).unwrap();
+    assert_eq!(recorded.lines().count(), 3);
+    assert!(scores.starts_with("easy:\n"), "{scores}");
+    assert!(scores.contains("custom:\n"), "{scores}");
+    assert_eq!(scores.matches("ada").count(), 2, "{scores}");
+}
+
+#[test]
+fn recorded_game_replays_from_the_command_line() {
+    let path = data_dir("replay").join("game.jsonl");
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    let path = path.to_str().unwrap();
+
+    let played = run(&["--seed", "3", "--record", path], &[], "50\n25\nquit\n");
+    assert_eq!(played.status.code(), Some(3));
+
+    let replayed = run(&["replay", path], &[], "");
+    assert!(replayed.status.success(), "{}", stdout(&replayed));
+    assert!(stdout(&replayed).contains("All 3 inputs replayed identically."));
+
+    let tampered =
+        fs::read_to_string(path)
+            .unwrap()
+            .replacen("\"line\":\"50\"", "\"line\":\"51\"", 1);
+    fs::write(path, tampered).unwrap();
+    let replayed = run(&["replay", path], &[], "");
+    assert_eq!(replayed.status.code(), Some(1));
+    assert!(stdout(&replayed).contains("MISMATCH at input 1"));
+}
+
+#[test]
+fn words_can_be_guessed_in_dictionary_order() {
+    let revealed = stdout(&run(
+        &["--kind", "word", "--seed", "9", "--reveal"],
+        &[],
+        "",
+    ));
+    let secret = revealed
+        .lines()
+        .next()
+        .and_then(|line| line.strip_prefix("Secret word is: "))
+        .unwrap();
+
+    let input = format!("able\nzipper\n{}\n", secret.to_uppercase());
+    let output = run(&["--kind", "word", "--seed", "9"], &[], &input);
+    let out = stdout(&output);
+    assert!(output.status.success(), "{out}");
+    assert!(out.contains("Later in the dictionary!"), "{out}");
+    assert!(out.contains("Earlier in the dictionary!"), "{out}");
+
+    let mixed = run(&["--kind", "word", "--hints", "distance"], &[], "");
+    assert_eq!(mixed.status.code(), Some(2));
+}
+
+#[test]
+fn lies_are_revealed_after_the_game() {
+    let output = run(&["--lies", "2", "--auto", "--seed", "5"], &[], "");
+    let out = stdout(&output);
+    assert!(output.status.success(), "{out}");
+    assert!(out.contains("up to 2 of my answers may be lies"), "{out}");
+    assert!(out.contains("Lies: "), "{out}");
+
+    let random = run(&["--lies", "1", "--auto", "random"], &[], "");
+    assert_eq!(random.status.code(), Some(2));
+}
+
+#[test]
+fn json_mode_speaks_one_event_per_line() {
+    let output = run(
+        &["--json", "--min", "42", "--max", "42"],
+        &[],
+        "{\"guess\":42}\n",
+    );
+    let out = stdout(&output);
+    assert!(output.status.success(), "{out}");
+
+    let events: Vec<serde_json::Value> = out
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(events.len(), 3, "{out}");
+    assert_eq!(events[0]["version"], 1);
+    assert_eq!(events[1]["result"], "correct");
+    assert_eq!(events[2]["outcome"], "won");
+}
+
+#[test]
+fn saved_games_resume_once() {
+    let data = data_dir("resume");
+    let _ = fs::remove_dir_all(&data);
+    let envs = [("XDG_DATA_HOME", data.to_str().unwrap())];
+
+    let saved = run(&["--seed", "5"], &envs, "50\nsave\n");
+    assert_eq!(saved.status.code(), Some(3));
+    assert!(stdout(&saved).contains("Game saved to"));
+    assert!(!stdout(&saved).contains("The secret number was"));
+
+    // A recording would start from a fresh game, not the saved one.
+    let record = data.join("resumed.jsonl");
+    let recorded = run(
+        &["--resume", "--record", record.to_str().unwrap()],
+        &envs,
+        "",
+    );
+    assert_eq!(recorded.status.code(), Some(2));
+
+    let resumed = run(&["--resume"], &envs, "q\n");
+    let out = stdout(&resumed);
+    assert!(out.contains("1 attempts used so far"), "{out}");
+    assert!(out.contains("Attempts: 1"), "{out}");
+
+    let again = run(&["--resume"], &envs, "");
+    assert_eq!(again.status.code(), Some(2));
+}
+
+#[test]
+fn games_that_cant_be_saved_say_so() {
+    let data = data_dir("unsaved");
+    let _ = fs::remove_dir_all(&data);
+    let envs = [("XDG_DATA_HOME", data.to_str().unwrap())];
+
+    for args in [
+        &["--kind", "word", "--seed", "9"][..],
+        &["--kind", "date", "--seed", "9"],
+        &["--game-time", "60", "--seed", "9"],
+        &["--daily", "2026-10-17"],
+    ] {
+        let output = run(args, &envs, "save\nq\n");
+        let out = stdout(&output);
+        assert!(out.contains("This game can't be saved"), "{args:?}: {out}");
+        assert_eq!(output.status.code(), Some(3), "{args:?}");
+    }
+    assert!(!data.join("guessing_game").join("save.json").exists());
+}
+
+#[test]
+fn weight_files_pick_the_secret() {
+    let path = data_dir("weights.txt");
+    fs::write(&path, "# only one candidate\n37 1\n").unwrap();
+
+    let output = run(
+        &["--weights", path.to_str().unwrap(), "--auto", "entropy"],
+        &[],
+        "",
+    );
+    let out = stdout(&output);
+    assert!(output.status.success(), "{out}");
+    assert!(out.contains("Bot guesses 37: You win!"), "{out}");
+    assert!(out.contains("Attempts: 1"), "{out}");
+    assert!(out.contains("replay with --weights "), "{out}");
+
+    // Transcripts only know the seed, so they can't replay weighted games.
+    let record = data_dir("weighted.jsonl");
+    let args = [
+        "--weights",
+        path.to_str().unwrap(),
+        "--record",
+        record.to_str().unwrap(),
+    ];
+    assert_eq!(run(&args, &[], "").status.code(), Some(2));
+
+    // Hot-seat games don't draw from distributions at all.
+    let args = ["--weights", path.to_str().unwrap(), "--players", "ann,bob"];
+    assert_eq!(run(&args, &[], "").status.code(), Some(2));
+}
+
+#[test]
+fn several_secrets_are_counted_until_all_are_found() {
+    let output = run(
+        &["--min", "1", "--max", "3", "--secrets", "3"],
+        &[],
+        "2\n1\n3\n",
+    );
+    let out = stdout(&output);
+    assert!(output.status.success(), "{out}");
+    assert!(out.contains("Below: 1, here: 1, above: 1"), "{out}");
+    assert!(out.contains("3 of 3 secrets found"), "{out}");
+
+    let bot = run(
+        &["--secrets", "5", "-d", "hard", "--auto", "--seed", "4"],
+        &[],
+        "",
+    );
+    assert!(bot.status.success(), "{}", stdout(&bot));
+
+    let crowded = run(&["--min", "1", "--max", "3", "--secrets", "4"], &[], "");
+    assert_eq!(crowded.status.code(), Some(2));
+
+    let huge = ["--max", "5000000000", "--secrets", "4000000000"];
+    assert_eq!(run(&huge, &[], "").status.code(), Some(2));
+}
+
+#[test]
+fn bots_play_the_full_range_of_numbers() {
+    for strategy in ["binary", "entropy"] {
+        let args = [
+            "--min",
+            "0",
+            "--max",
+            "18446744073709551615",
+            "--auto",
+            strategy,
+        ];
+        let output = run(&args, &[], "");
+        assert!(output.status.success(), "{}", stdout(&output));
+    }
+}
+
+#[cfg(feature = "tui")]
+#[test]
+fn clues_are_refused_full_screen() {
+    let output = run(&["--tui", "--hints", "clues"], &[], "");
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("--hints clues"));
+}
+
+#[test]
+fn simulation_tables_are_translated() {
+    let output = run(
+        &[
+            "simulate", "--lang", "de", "--games", "5", "--max", "20", "--seed", "1",
+        ],
+        &[],
+        "",
+    );
+    let table = stdout(&output);
+    assert!(
+        table.starts_with("5 Spiele pro Strategie von 1 bis 20"),
+        "{table}"
+    );
+    assert!(table.contains("Versuche mit binary:"), "{table}");
+    assert!(!table.contains("attempts"), "{table}");
+}
+
+#[test]
+fn huge_date_ranges_start_straight_away() {
+    let output = run(
+        &[
+            "--kind",
+            "date",
+            "--from",
+            "0001-01-01",
+            "--to",
+            "2000000-12-31",
+            "--seed",
+            "3",
+        ],
+        &[],
+        "quit\n",
+    );
+    assert_eq!(output.status.code(), Some(3));
+    assert!(stdout(&output).contains("from 0001-01-01 to 2000000-12-31"));
+}
+
+#[cfg(feature = "tui")]
+#[test]
+fn resumed_games_are_not_full_screen() {
+    let output = run(&["--resume", "--tui"], &[], "");
+    assert_eq!(output.status.code(), Some(2));
+}
@@ -0,0 +1,52 @@
+use guessing_game::daily::{self, DailySecret, Date};
+use guessing_game::i18n::Messages;
+use guessing_game::{Game, SeededSecret};
+
+#[test]
+fn days_since_the_epoch_become_calendar_dates() {
+    assert_eq!(Date::from_unix_days(0).to_string(), "1970-01-01");
+    assert_eq!(Date::from_unix_days(-1).to_string(), "1969-12-31");
+    assert_eq!(Date::from_unix_days(10_957).to_string(), "2000-01-01");
+    assert_eq!(Date::from_unix_days(11_016).to_string(), "2000-02-29");
+    assert_eq!(Date::from_unix_days(20_743).to_string(), "2026-10-17");
+    for days in [-800_000, -1, 0, 59, 10_957, 11_016, 20_743, 2_000_000] {
+        assert_eq!(Date::from_unix_days(days).unix_days(), days);
+    }
+
+    assert_eq!("2024-02-29".parse(), Ok(Date::new(2024, 2, 29)));
+    assert!("2023-02-29".parse::<Date>().is_err());
+    assert!("2026-13-01".parse::<Date>().is_err());
+    assert!("17/10/2026".parse::<Date>().is_err());
+}
+
+#[test]
+fn every_player_gets_the_same_secret_for_a_date() {
+    let date = Date::new(2026, 10, 17);
+    let first = Game::new(1..=1000, DailySecret::new(date));
+    let second = Game::new(1..=1000, DailySecret::new(date));
+    assert_eq!(first.secret(), second.secret());
+
+    // The reported seed replays the daily puzzle like any other game.
+    let replay = Game::new(1..=1000, SeededSecret::new(first.seed().unwrap()));
+    assert_eq!(replay.secret(), first.secret());
+
+    let secrets: Vec<u64> = (1..=28)
+        .map(|day| Game::new(1..=1000, DailySecret::new(Date::new(2026, 2, day))).secret())
+        .collect();
+    assert!(secrets.iter().any(|&secret| secret != secrets[0]));
+}
+
+#[test]
+fn share_string_shows_directions_but_not_numbers() {
+    let date = Date::new(2026, 10, 17);
+    let mut game = Game::new(1..=100, DailySecret::new(date)).with_max_attempts(8);
+    let secret = game.secret();
+
+    game.guess(secret.saturating_sub(1).max(1));
+    game.guess(secret);
+
+    let share = daily::share(&game, date, &Messages::default());
+    assert!(share.starts_with("Guessing Game 2026-10-17 "), "{share}");
+    assert!(share.ends_with('🟩'), "{share}");
+    assert!(!share.contains(&format!(" {secret}")), "{share}");
+}
@@ -0,0 +1,115 @@
+use guessing_game::distribution::{
+    CumulativeWeights, DistributedSecret, Distribution, EntropySolver,
+};
+use guessing_game::simulate::Simulation;
+use guessing_game::solver::{self, Strategy};
+use guessing_game::{FixedSecret, Game, SecretSource, SeededSecret};
+
+fn mean_secret(distribution: Distribution) -> f64 {
+    let mut secrets = DistributedSecret::new(distribution, 5);
+    (0..2000)
+        .map(|_| secrets.pick(&(1..=100)) as f64)
+        .sum::<f64>()
+        / 2000.0
+}
+
+#[test]
+fn uniform_secrets_match_seeded_ones() {
+    let mut seeded = SeededSecret::new(9);
+    let mut distributed = DistributedSecret::new(Distribution::Uniform, 9);
+    for _ in 0..100 {
+        assert_eq!(distributed.pick(&(1..=1000)), seeded.pick(&(1..=1000)));
+        assert_eq!(distributed.seed(), seeded.seed());
+    }
+}
+
+#[test]
+fn secrets_follow_their_distribution() {
+    let normal = mean_secret(Distribution::Normal);
+    assert!((45.0..56.0).contains(&normal), "{normal}");
+
+    let exponential = mean_secret(Distribution::Exponential);
+    assert!(exponential < 30.0, "{exponential}");
+
+    let weights = Distribution::load("# lucky\n7 3\n\n13 1\n".as_bytes()).unwrap();
+    let mut secrets = DistributedSecret::new(weights, 5);
+    for _ in 0..100 {
+        assert!([7, 13].contains(&secrets.pick(&(1..=100))));
+    }
+}
+
+#[test]
+fn malformed_weight_files_are_rejected() {
+    for file in ["7\n", "7 1 2\n", "x 1\n", "7 -1\n", "7 NaN\n", "7 1\n7 2\n"] {
+        assert!(Distribution::load(file.as_bytes()).is_err(), "{file:?}");
+    }
+
+    let outside = Distribution::load("7 1\n500 1\n".as_bytes()).unwrap();
+    assert!(outside.check(&(1..=100)).is_err());
+    let zero = Distribution::load("7 0\n".as_bytes()).unwrap();
+    assert!(zero.check(&(1..=100)).is_err());
+    assert!(Distribution::Normal.check(&(1..=u64::MAX)).is_err());
+    assert!(Distribution::Uniform.check(&(1..=u64::MAX)).is_ok());
+}
+
+#[test]
+fn entropy_solver_finds_every_secret() {
+    let weights = Distribution::load("7 10\n13 5\n42 1\n".as_bytes()).unwrap();
+    for distribution in Distribution::NAMED.into_iter().chain([weights]) {
+        for secret in 1..=100 {
+            let mut game = Game::new(1..=100, FixedSecret(secret));
+            let mut bot = EntropySolver::new(1..=100, &distribution);
+            solver::solve(&mut game, &mut bot).unwrap();
+
+            assert!(game.is_won(), "{secret} from {distribution}");
+        }
+    }
+}
+
+#[test]
+fn entropy_solver_beats_binary_search_on_skewed_secrets() {
+    for distribution in [Distribution::Normal, Distribution::Exponential] {
+        let simulation = Simulation {
+            range: 1..=100,
+            max_attempts: None,
+            games: 1000,
+            seed: 3,
+            distribution,
+        };
+
+        let binary = simulation.run(Strategy::Binary).unwrap();
+        let entropy = simulation.run(Strategy::Entropy).unwrap();
+        assert!(
+            entropy.mean < binary.mean,
+            "{} vs {}",
+            entropy.mean,
+            binary.mean
+        );
+    }
+}
+
+#[test]
+fn shared_weights_play_like_fresh_ones() {
+    let range = 1..=99_999;
+    let weights = CumulativeWeights::new(&range, &Distribution::Normal);
+    let mut secrets = DistributedSecret::new(Distribution::Normal, 8);
+
+    for _ in 0..50 {
+        let secret = secrets.pick(&range);
+        let mut fresh = Game::new(range.clone(), FixedSecret(secret));
+        let mut shared = Game::new(range.clone(), FixedSecret(secret));
+        let fresh_turns = solver::solve(
+            &mut fresh,
+            &mut EntropySolver::new(range.clone(), &Distribution::Normal),
+        )
+        .unwrap();
+        let shared_turns = solver::solve(
+            &mut shared,
+            &mut EntropySolver::with_weights(range.clone(), &weights),
+        )
+        .unwrap();
+
+        assert!(shared.is_won());
+        assert_eq!(shared_turns, fresh_turns);
+    }
+}
@@ -0,0 +1,143 @@
+use guessing_game::daily::Date;
+use guessing_game::hotseat::HotSeat;
+use guessing_game::{Difficulty, FixedSecret, Game, Outcome};
+
+use std::ops::Bound;
+
+#[test]
+fn guesses_are_compared_with_the_secret() {
+    let mut game = Game::new(1..=100, FixedSecret(42));
+
+    assert_eq!(game.guess(10), Outcome::TooSmall);
+    assert_eq!(game.guess(90), Outcome::TooBig);
+    assert_eq!(game.attempts(), 2);
+    assert!(!game.is_over());
+
+    assert_eq!(game.guess(42), Outcome::Correct);
+    assert_eq!(game.attempts(), 3);
+    assert!(game.is_won());
+    assert_eq!(game.guess(42), Outcome::GameOver);
+    assert_eq!(game.attempts(), 3);
+}
+
+#[test]
+fn out_of_range_guesses_are_free() {
+    let mut game = Game::new(10..=20, FixedSecret(15)).with_max_attempts(1);
+
+    assert_eq!(game.guess(9), Outcome::OutOfRange);
+    assert_eq!(game.guess(21), Outcome::OutOfRange);
+    assert_eq!(game.attempts(), 0);
+    assert_eq!(game.remaining_attempts(), Some(1));
+    assert_eq!(game.guess(15), Outcome::Correct);
+}
+
+#[test]
+fn difficulty_presets_set_range_and_attempts() {
+    let presets: Vec<_> = Difficulty::ALL
+        .iter()
+        .map(|d| (d.range(), d.max_attempts()))
+        .collect();
+    assert_eq!(presets, [(1..=20, 10), (1..=100, 8), (1..=1000, 10)]);
+
+    assert_eq!("Hard".parse(), Ok(Difficulty::Hard));
+    assert_eq!(Difficulty::Normal.to_string(), "normal");
+    assert!("impossible".parse::<Difficulty>().is_err());
+}
+
+#[test]
+fn running_out_of_attempts_loses_the_game() {
+    let mut game = Game::new(1..=100, FixedSecret(42)).with_max_attempts(2);
+
+    assert_eq!(game.guess(10), Outcome::TooSmall);
+    assert_eq!(game.remaining_attempts(), Some(1));
+    assert_eq!(game.guess(90), Outcome::TooBig);
+
+    assert!(game.is_lost());
+    assert_eq!(game.remaining_attempts(), Some(0));
+    assert_eq!(game.guess(42), Outcome::GameOver);
+    assert!(!game.is_won());
+}
+
+#[test]
+fn ruled_out_guesses_are_flagged() {
+    let mut charged = Game::new(1..=100, FixedSecret(42));
+    charged.guess(50);
+    assert_eq!(charged.guess(50), Outcome::RuledOut);
+    assert_eq!(charged.guess(60), Outcome::RuledOut);
+    assert_eq!(charged.attempts(), 3);
+
+    let mut free = Game::new(1..=100, FixedSecret(42)).with_free_repeats(true);
+    free.guess(50);
+    assert_eq!(free.guess(50), Outcome::RuledOut);
+    assert_eq!(free.attempts(), 1);
+    assert_eq!(free.guess(0), Outcome::OutOfRange);
+    assert_eq!(free.guess(42), Outcome::Correct);
+}
+
+#[test]
+fn any_ordered_secret_can_be_guessed() {
+    // Sorted, the candidates are apple, fig, pear, so position 1 is fig.
+    let mut game = Game::from_candidates(["pear", "apple", "fig", "apple"], FixedSecret(1));
+    assert_eq!(game.range(), &("apple"..="pear"));
+    assert_eq!(game.secret(), "fig");
+
+    assert_eq!(game.guess("banana"), Outcome::TooSmall);
+    assert_eq!(game.guess("grape"), Outcome::TooBig);
+    assert_eq!(
+        game.bounds(),
+        (Bound::Excluded(&"banana"), Bound::Excluded(&"grape"))
+    );
+    assert_eq!(game.guess("apricot"), Outcome::RuledOut);
+    assert_eq!(game.guess("zucchini"), Outcome::OutOfRange);
+    assert_eq!(game.guess("fig"), Outcome::Correct);
+    assert_eq!(game.attempts(), 4);
+
+    let days = (0..365).map(Date::from_unix_days);
+    let mut game = Game::from_candidates(days, FixedSecret(31)).with_max_attempts(1);
+    assert_eq!(game.secret(), Date::new(1970, 2, 1));
+    assert_eq!(game.guess(Date::new(1970, 1, 31)), Outcome::TooSmall);
+    assert!(game.is_lost());
+}
+
+#[test]
+fn sequences_pick_like_candidates_without_listing_them() {
+    let listed = Game::from_candidates((0..365).map(Date::from_unix_days), FixedSecret(31));
+    let counted = Game::from_sequence(365, |day| Date::from_unix_days(day as i64), FixedSecret(31));
+    assert_eq!(counted.secret(), listed.secret());
+    assert_eq!(counted.range(), listed.range());
+
+    // Millions of years of days, far too many to hold in memory at once.
+    let first = Date::new(1, 1, 1).unix_days();
+    let len = (Date::new(2_000_000, 12, 31).unix_days() - first) as u64 + 1;
+    let mut game = Game::from_sequence(
+        len,
+        |day| Date::from_unix_days(first + day as i64),
+        FixedSecret(len - 1),
+    );
+    assert_eq!(game.secret(), Date::new(2_000_000, 12, 31));
+    assert_eq!(game.guess(Date::new(1, 1, 1)), Outcome::TooSmall);
+}
+
+#[test]
+fn hot_seat_rotates_turns_and_skips_players_out_of_attempts() {
+    let game = Game::new(1..=100, FixedSecret(42));
+    let names = vec!["ann".to_string(), "bob".to_string()];
+    let mut hotseat = HotSeat::new(game, names, Some(2));
+
+    assert_eq!(hotseat.current().name, "ann");
+    assert_eq!(hotseat.guess(0), Outcome::OutOfRange);
+    assert_eq!(hotseat.current().name, "ann");
+    hotseat.guess(10);
+    hotseat.guess(90);
+    hotseat.guess(20);
+    hotseat.guess(80);
+    assert!(hotseat.is_over());
+    assert!(hotseat.winner().is_none());
+
+    let game = Game::new(1..=100, FixedSecret(42));
+    let mut hotseat = HotSeat::new(game, vec!["ann".into(), "bob".into()], None);
+    hotseat.guess(10);
+    assert_eq!(hotseat.guess(42), Outcome::Correct);
+    assert_eq!(hotseat.winner().unwrap().name, "bob");
+    assert_eq!(hotseat.players()[1].attempts, 1);
+}
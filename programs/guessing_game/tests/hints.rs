@@ -0,0 +1,37 @@
+use guessing_game::hints::{Clue, ClueAnswer, Hint, HintMode};
+use guessing_game::{FixedSecret, Game};
+
+#[test]
+fn hints_follow_enabled_modes() {
+    let mut game = Game::new(1..=100, FixedSecret(40)).with_hints(HintMode::ALL);
+
+    game.guess(10);
+    assert_eq!(game.hints(), [Hint::Within(50), Hint::Interval(11, 100)]);
+
+    game.guess(45);
+    assert_eq!(
+        game.hints(),
+        [Hint::Warmer, Hint::Within(5), Hint::Interval(11, 44)]
+    );
+
+    game.guess(40);
+    assert!(game.hints().is_empty());
+}
+
+#[test]
+fn clues_cost_an_attempt_and_need_the_clues_mode() {
+    let mut plain = Game::new(1..=100, FixedSecret(42));
+    assert_eq!(plain.buy_clue(Clue::Parity), None);
+    assert_eq!(plain.attempts(), 0);
+
+    let mut game = Game::new(1..=100, FixedSecret(42))
+        .with_hints([HintMode::Clues])
+        .with_max_attempts(2);
+    assert_eq!(game.buy_clue(Clue::Parity), Some(ClueAnswer::Even(true)));
+    assert_eq!(
+        game.buy_clue("div 5".parse().unwrap()),
+        Some(ClueAnswer::DivisibleBy(5, false))
+    );
+    assert!(game.is_lost());
+    assert_eq!(game.buy_clue(Clue::Parity), None);
+}
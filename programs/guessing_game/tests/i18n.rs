@@ -0,0 +1,89 @@
+use guessing_game::i18n::{self, Locale, Messages};
+
+#[test]
+fn every_catalog_has_every_key() {
+    let english = Locale::En.catalog();
+    for locale in Locale::ALL {
+        let catalog = locale.catalog();
+        for key in english.keys() {
+            assert!(catalog.get(key).is_some(), "{locale} is missing '{key}'");
+        }
+        for key in catalog.keys() {
+            assert!(
+                english.get(key).is_some(),
+                "{locale} has unknown key '{key}'"
+            );
+        }
+    }
+}
+
+#[test]
+fn every_catalog_has_the_report_texts() {
+    // Scores, simulations and the daily share line are printed without a
+    // game in progress, so check them by name.
+    let keys = [
+        "scores_custom",
+        "scores_difficulty",
+        "scores_kind",
+        "scores_kind_difficulty",
+        "scores_row",
+        "score_save_failed",
+        "difficulty_easy",
+        "difficulty_normal",
+        "difficulty_hard",
+        "kind_number",
+        "kind_word",
+        "kind_date",
+        "share_title",
+        "simulate_intro",
+        "simulate_strategy",
+        "simulate_games",
+        "simulate_wins",
+        "simulate_mean",
+        "simulate_median",
+        "simulate_worst",
+        "simulate_histogram",
+    ];
+    for locale in Locale::ALL {
+        let catalog = locale.catalog();
+        for key in keys {
+            assert!(catalog.get(key).is_some(), "{locale} is missing '{key}'");
+        }
+    }
+}
+
+#[test]
+fn translations_use_the_same_placeholders() {
+    let english = Locale::En.catalog();
+    for locale in Locale::ALL {
+        let catalog = locale.catalog();
+        for key in english.keys() {
+            let mut expected = i18n::placeholders(english.get(key).unwrap());
+            let mut found = i18n::placeholders(catalog.get(key).unwrap_or_default());
+            expected.sort_unstable();
+            found.sort_unstable();
+            assert_eq!(found, expected, "{locale} '{key}'");
+        }
+    }
+}
+
+#[test]
+fn locales_parse_from_posix_names() {
+    assert_eq!("de_DE.UTF-8".parse(), Ok(Locale::De));
+    assert_eq!("de".parse(), Ok(Locale::De));
+    assert_eq!("en_GB".parse(), Ok(Locale::En));
+    assert_eq!("C".parse(), Ok(Locale::En));
+    assert!("xx_XX".parse::<Locale>().is_err());
+}
+
+#[test]
+fn placeholders_are_filled_in() {
+    let messages = Messages::new(Locale::De);
+    let message = messages.format("input_range", &[("min", &1), ("max", &100)]);
+
+    assert!(
+        message.contains("1") && message.contains("100"),
+        "{message}"
+    );
+    assert!(!message.contains('{'), "{message}");
+}
@@ -0,0 +1,95 @@
+use guessing_game::multi::{self, Count, MultiGame, MultiOutcome, MultiSolver};
+use guessing_game::{SecretSource, SeededSecret};
+
+use std::ops::RangeInclusive;
+
+/// Hands out the given secrets in order.
+struct Secrets(Vec<u64>);
+
+impl SecretSource for Secrets {
+    fn pick(&mut self, _range: &RangeInclusive<u64>) -> u64 {
+        self.0.remove(0)
+    }
+}
+
+#[test]
+fn guesses_count_the_secrets_on_each_side() {
+    let mut game = MultiGame::new(1..=100, 3, Secrets(vec![70, 20, 40]));
+    assert_eq!(game.secrets(), [20, 40, 70]);
+
+    let count = |below, equal, above| {
+        MultiOutcome::Counted(Count {
+            below,
+            equal,
+            above,
+        })
+    };
+    assert_eq!(game.guess(50), count(2, 0, 1));
+    assert_eq!(game.guess(40), count(1, 1, 1));
+    assert_eq!(game.guess(30), count(1, 0, 2));
+    assert_eq!(game.guess(60), count(2, 0, 1));
+    assert_eq!(game.guess(101), MultiOutcome::OutOfRange);
+    assert_eq!(game.found(), [40]);
+    assert_eq!(game.attempts(), 4);
+
+    assert_eq!(game.guess(20), count(0, 1, 2));
+    assert_eq!(game.guess(70), count(2, 1, 0));
+    assert!(game.is_won());
+    assert_eq!(game.guess(1), MultiOutcome::GameOver);
+}
+
+#[test]
+fn running_out_of_attempts_loses() {
+    let mut game = MultiGame::new(1..=10, 2, Secrets(vec![3, 7])).with_max_attempts(2);
+    game.guess(3);
+    game.guess(5);
+
+    assert!(game.is_lost());
+    assert_eq!(game.found(), [3]);
+}
+
+#[test]
+fn same_seed_hides_the_same_secrets() {
+    let first = MultiGame::new(1..=1000, 5, SeededSecret::new(11));
+    let second = MultiGame::new(1..=1000, 5, SeededSecret::new(11));
+
+    assert_eq!(first.secrets(), second.secrets());
+    assert_eq!(first.secrets().len(), 5);
+    assert_eq!(first.seed(), Some(11));
+}
+
+#[test]
+fn every_secret_set_is_solved_within_worst_case_bound() {
+    // Every subset of every small range.
+    for len in 1..=10u64 {
+        for mask in 1..1u32 << len {
+            let secrets: Vec<u64> = (0..len)
+                .filter(|i| mask & 1 << i != 0)
+                .map(|i| i + 1)
+                .collect();
+            let count = secrets.len();
+            let mut game = MultiGame::new(1..=len, count, Secrets(secrets.clone()));
+            let mut bot = MultiSolver::new(1..=len, count);
+            let turns = multi::solve(&mut game, &mut bot).unwrap();
+
+            assert!(game.is_won(), "{secrets:?}");
+            assert!(
+                turns.len() as u32 <= MultiSolver::worst_case(len, count),
+                "{secrets:?} took {}",
+                turns.len()
+            );
+        }
+    }
+
+    // Seeded sets in larger ranges.
+    for (max, count) in [(100, 1), (100, 5), (100, 100), (1000, 10), (1000, 50)] {
+        for seed in 0..50 {
+            let mut game = MultiGame::new(1..=max, count, SeededSecret::new(seed));
+            let mut bot = MultiSolver::new(1..=max, count);
+            let turns = multi::solve(&mut game, &mut bot).unwrap();
+
+            assert!(game.is_won());
+            assert!(turns.len() as u32 <= MultiSolver::worst_case(max, count));
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use guessing_game::console::Input;
+use guessing_game::number::{self, NumberError};
+
+#[test]
+fn literals_and_words_parse_to_the_same_number() {
+    for input in [
+        "42",
+        " 42 ",
+        "+42",
+        "0x2A",
+        "0X2a",
+        "0b101010",
+        "0o52",
+        "4_2",
+        "42.0",
+        "forty-two",
+        " Forty Two ",
+    ] {
+        assert_eq!(number::parse(input), Ok(42), "{input:?}");
+    }
+    assert_eq!(number::parse("1_000"), Ok(1000));
+    assert_eq!(number::parse("one hundred and five"), Ok(105));
+    assert_eq!(number::parse("a thousand"), Ok(1000));
+    assert_eq!(number::parse("two million three thousand"), Ok(2_003_000));
+    assert_eq!(number::parse("-0"), Ok(0));
+    assert_eq!(number::parse("zero"), Ok(0));
+    assert_eq!(number::parse("hundred"), Ok(100));
+    assert_eq!(number::parse("nine hundred ninety-nine"), Ok(999));
+    assert_eq!(
+        number::parse("one million two hundred thousand and seventeen"),
+        Ok(1_200_017)
+    );
+}
+
+#[test]
+fn number_words_follow_english_grammar() {
+    for input in [
+        "twenty twenty",
+        "one two three",
+        "seven eleven",
+        "twenty zero",
+        "thousand thousand",
+        "five hundred hundred",
+        "ten hundred",
+        "twenty hundred",
+        "one thousand million",
+        "zero thousand",
+        "three and four",
+    ] {
+        assert_eq!(
+            number::parse(input),
+            Err(NumberError::NotANumber),
+            "{input:?}"
+        );
+    }
+}
+
+#[test]
+fn bad_numbers_say_what_is_wrong() {
+    assert_eq!(number::parse(""), Err(NumberError::Empty));
+    assert_eq!(number::parse("-5"), Err(NumberError::Negative));
+    assert_eq!(number::parse("minus five"), Err(NumberError::Negative));
+    assert_eq!(
+        number::parse("99999999999999999999"),
+        Err(NumberError::Overflow)
+    );
+    assert_eq!(
+        number::parse("twenty quintillion"),
+        Err(NumberError::Overflow)
+    );
+    assert_eq!(number::parse("42.5"), Err(NumberError::Fractional));
+    assert_eq!(number::parse("abc"), Err(NumberError::NotANumber));
+    assert_eq!(number::parse("0xZZ"), Err(NumberError::NotANumber));
+    assert_eq!(number::parse("-"), Err(NumberError::NotANumber));
+}
+
+#[test]
+fn console_input_keeps_clues_and_quit_apart_from_numbers() {
+    assert_eq!(Input::parse("0x10"), Input::Guess(16));
+    assert_eq!(Input::parse("q"), Input::Quit);
+    assert!(matches!(Input::parse("div 3"), Input::Clue(_)));
+    assert_eq!(Input::parse("-3"), Input::Invalid(NumberError::Negative));
+}
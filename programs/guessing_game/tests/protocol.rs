@@ -0,0 +1,112 @@
+use guessing_game::protocol::{self, Ending, ErrorKind, Event, Request, VERSION, Verdict};
+use guessing_game::{FixedSecret, Game};
+
+fn play(game: &mut Game, requests: &str) -> Vec<Event> {
+    let mut out = Vec::new();
+    protocol::play(game, requests.as_bytes(), &mut out, false).unwrap();
+    String::from_utf8(out)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect()
+}
+
+#[test]
+fn every_request_gets_one_event() {
+    let mut game = Game::new(1..=100, FixedSecret(25)).with_max_attempts(5);
+    let events = play(
+        &mut game,
+        "{\"guess\":50}\n\n{\"guess\":25}\n{\"guess\":1}\n",
+    );
+
+    assert_eq!(
+        events,
+        [
+            Event::Start {
+                version: VERSION,
+                min: 1,
+                max: 100,
+                max_attempts: Some(5),
+                max_lies: None,
+                secret: None,
+            },
+            Event::Result {
+                result: Verdict::High,
+                attempts: 1,
+                remaining: Some(4),
+            },
+            Event::Result {
+                result: Verdict::Correct,
+                attempts: 2,
+                remaining: Some(3),
+            },
+            Event::End {
+                outcome: Ending::Won,
+                secret: 25,
+                attempts: 2,
+                lies: vec![],
+            },
+        ]
+    );
+}
+
+#[test]
+fn bad_lines_are_errors_that_cost_nothing() {
+    let mut game = Game::new(1..=100, FixedSecret(25));
+    let events = play(
+        &mut game,
+        "50\n{\"guess\":-1}\n{\"guess\":\"7\"}\n{\"pick\":7}\n",
+    );
+
+    let kinds: Vec<_> = events
+        .iter()
+        .filter_map(|event| match event {
+            Event::Error { kind, .. } => Some(*kind),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        kinds,
+        [
+            ErrorKind::Parse,
+            ErrorKind::Request,
+            ErrorKind::Request,
+            ErrorKind::Request
+        ]
+    );
+    assert_eq!(game.attempts(), 0);
+}
+
+#[test]
+fn quitting_or_hanging_up_ends_the_game() {
+    for requests in [
+        "{\"guess\":50}\n{\"quit\":null}\n{\"guess\":25}\n",
+        "{\"guess\":50}\n",
+    ] {
+        let mut game = Game::new(1..=100, FixedSecret(25));
+        let events = play(&mut game, requests);
+
+        assert_eq!(events.len(), 3);
+        assert!(matches!(
+            events.last(),
+            Some(Event::End {
+                outcome: Ending::Quit,
+                secret: 25,
+                attempts: 1,
+                ..
+            })
+        ));
+    }
+}
+
+#[test]
+fn requests_serialize_as_documented() {
+    assert_eq!(
+        serde_json::to_string(&Request::Guess(42)).unwrap(),
+        r#"{"guess":42}"#
+    );
+    assert_eq!(
+        serde_json::from_str::<Request>(r#"{"quit":null}"#).unwrap(),
+        Request::Quit
+    );
+}
@@ -0,0 +1,34 @@
+use guessing_game::reverse::{Reply, ReverseGame};
+
+#[test]
+fn honest_answers_find_every_number() {
+    for secret in 1..=100 {
+        let mut game = ReverseGame::new(1..=100);
+        while !game.is_found() {
+            let guess = game.next_guess().unwrap();
+            let reply = match secret.cmp(&guess) {
+                std::cmp::Ordering::Greater => Reply::Higher,
+                std::cmp::Ordering::Less => Reply::Lower,
+                std::cmp::Ordering::Equal => Reply::Correct,
+            };
+            game.answer(guess, reply);
+        }
+        assert!(game.attempts() <= 7);
+    }
+}
+
+#[test]
+fn contradictory_answers_are_called_out() {
+    let mut game = ReverseGame::new(1..=100);
+    let mut replies = ["higher", "lower", "lower", "lower", "lower", "lower"].iter();
+
+    let contradiction = loop {
+        match game.next_guess() {
+            Ok(guess) => game.answer(guess, replies.next().unwrap().parse().unwrap()),
+            Err(contradiction) => break contradiction,
+        }
+    };
+
+    assert_eq!(contradiction.higher_than, Some(50));
+    assert_eq!(contradiction.lower_than, Some(51));
+}
@@ -0,0 +1,84 @@
+use guessing_game::console;
+use guessing_game::i18n::{Locale, Messages};
+use guessing_game::save::SavedGame;
+use guessing_game::ulam::Liar;
+use guessing_game::{Difficulty, Game, SeededSecret};
+
+use std::time::Duration;
+
+fn save(game: &mut Game, lines: &[&str]) -> Vec<u8> {
+    let messages = Messages::new(Locale::En);
+    let mut saved = SavedGame::of(game, Some(Difficulty::Easy)).unwrap();
+    for line in lines {
+        console::respond(game, line, &messages);
+        saved.record(line);
+    }
+    saved.set_elapsed(Duration::from_millis(1500));
+
+    let mut file = Vec::new();
+    saved.write(&mut file).unwrap();
+    file
+}
+
+#[test]
+fn resumed_games_continue_where_they_left_off() {
+    let mut game = Game::new(1..=100, SeededSecret::new(7))
+        .with_max_attempts(5)
+        .with_lies(Liar::new(1, 7));
+    let file = save(&mut game, &["50", "nonsense", "25"]);
+
+    let saved = SavedGame::load(file.as_slice()).unwrap();
+    assert_eq!(saved.difficulty, Some(Difficulty::Easy));
+    assert_eq!(saved.elapsed(), Duration::from_millis(1500));
+
+    let resumed = saved.game().unwrap();
+    assert_eq!(resumed.secret(), game.secret());
+    assert_eq!(resumed.seed(), Some(7));
+    assert_eq!(resumed.attempts(), 2);
+    assert_eq!(resumed.remaining_attempts(), Some(3));
+    assert_eq!(resumed.guesses(), game.guesses());
+    assert_eq!(resumed.lies(), game.lies());
+}
+
+#[test]
+fn the_secret_is_not_stored_in_the_clear() {
+    let mut game = Game::new(1..=100, SeededSecret::new(7));
+    let file = String::from_utf8(save(&mut game, &[])).unwrap();
+
+    assert!(!file.contains("secret"), "{file}");
+    assert!(!file.contains("\"seed\""), "{file}");
+}
+
+#[test]
+fn edited_saves_are_rejected() {
+    let mut game = Game::new(1..=100, SeededSecret::new(7));
+    let file = String::from_utf8(save(&mut game, &["50"])).unwrap();
+
+    let sealed_at = file.find("\"sealed\":\"").unwrap() + "\"sealed\":\"".len();
+    let flipped = if &file[sealed_at..=sealed_at] == "0" {
+        "1"
+    } else {
+        "0"
+    };
+    let edits = [
+        file.replace("\"max\":100", "\"max\":10"),
+        file.replace("[\"50\"]", "[\"50\",\"51\"]"),
+        file.replace("\"elapsed_ms\":1500", "\"elapsed_ms\":1"),
+        format!("{}{flipped}{}", &file[..sealed_at], &file[sealed_at + 1..]),
+    ];
+    for edited in edits {
+        assert_ne!(edited, file);
+        let err = SavedGame::load(edited.as_bytes()).unwrap_err();
+        assert!(err.to_string().contains("tampered"), "{err}");
+    }
+}
+
+#[test]
+fn finished_games_cannot_be_resumed() {
+    let mut game = Game::new(1..=100, SeededSecret::new(7));
+    let secret = game.secret().to_string();
+    let file = save(&mut game, &[&secret]);
+
+    let saved = SavedGame::load(file.as_slice()).unwrap();
+    assert!(saved.game().is_err());
+}
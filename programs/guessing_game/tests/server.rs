@@ -0,0 +1,168 @@
+use guessing_game::server::{Server, ServerConfig};
+use guessing_game::{FixedSecret, SecretSource, SeededSecret};
+
+use std::io::{BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpStream};
+use std::thread;
+use std::time::Duration;
+
+fn start(config: ServerConfig, secrets: impl SecretSource + Send + 'static) -> SocketAddr {
+    let server = Server::bind("127.0.0.1:0", config, secrets).unwrap();
+    let addr = server.local_addr().unwrap();
+    thread::spawn(move || server.run());
+    addr
+}
+
+struct Client {
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+}
+
+impl Client {
+    fn connect(addr: SocketAddr) -> Self {
+        let writer = TcpStream::connect(addr).unwrap();
+        let reader = BufReader::new(writer.try_clone().unwrap());
+        Self { reader, writer }
+    }
+
+    fn read(&mut self) -> String {
+        let mut line = String::new();
+        self.reader.read_line(&mut line).unwrap();
+        line.trim_end().to_string()
+    }
+
+    fn send(&mut self, command: &str) -> String {
+        self.writer
+            .write_all(format!("{command}\n").as_bytes())
+            .unwrap();
+        self.read()
+    }
+}
+
+#[test]
+fn independent_games_over_loopback() {
+    let config = ServerConfig {
+        range: 1..=100,
+        max_attempts: Some(3),
+        race: false,
+        idle_timeout: None,
+        max_clients: 8,
+    };
+    let addr = start(config, FixedSecret(42));
+
+    let mut first = Client::connect(addr);
+    let mut second = Client::connect(addr);
+    assert_eq!(first.read(), "HELLO 1 100 3");
+    assert_eq!(second.read(), "HELLO 1 100 3");
+
+    assert_eq!(first.send("GUESS 50"), "HIGH");
+    assert_eq!(first.send("guess 10"), "LOW");
+    assert_eq!(first.send("GUESS 0"), "OUT");
+    assert_eq!(first.send("GUESS forty"), "ERR 'forty' is not a number");
+    assert_eq!(first.send("GUESS 42"), "WIN 3");
+
+    // A client vanishing mid-game doesn't disturb anyone else.
+    drop(first);
+    assert_eq!(second.send("GUESS 1"), "LOW");
+    assert_eq!(second.send("GUESS 2"), "LOW");
+    assert_eq!(second.send("GUESS 3"), "LOSE 42");
+    assert_eq!(
+        second.send("GUESS 42"),
+        "ERR game over, send NEW to play again"
+    );
+    assert_eq!(second.send("NEW"), "HELLO 1 100 3");
+    assert_eq!(second.send("QUIT"), "BYE 42");
+}
+
+#[test]
+fn race_ends_the_round_for_everyone() {
+    let config = ServerConfig {
+        range: 1..=100,
+        max_attempts: None,
+        race: true,
+        idle_timeout: None,
+        max_clients: 8,
+    };
+    let addr = start(config, SeededSecret::new(9));
+
+    let mut ann = Client::connect(addr);
+    let mut bob = Client::connect(addr);
+    ann.read();
+    bob.read();
+    ann.send("NAME ann");
+
+    // Binary search until ann finds the shared secret.
+    let (mut low, mut high) = (1, 100);
+    let secret = loop {
+        let guess = (low + high) / 2;
+        match ann.send(&format!("GUESS {guess}")).as_str() {
+            "LOW" => low = guess + 1,
+            "HIGH" => high = guess - 1,
+            reply => {
+                assert!(reply.starts_with("WIN "), "{reply}");
+                break guess;
+            }
+        }
+    };
+
+    assert_eq!(bob.send("GUESS 50"), format!("OVER ann {secret}"));
+    assert_eq!(bob.read(), "HELLO 1 100");
+    assert_eq!(bob.send("QUIT"), "BYE");
+}
+
+#[test]
+fn race_attempts_follow_the_name_and_the_round_ends_when_all_are_out() {
+    let config = ServerConfig {
+        range: 1..=100,
+        max_attempts: Some(1),
+        race: true,
+        idle_timeout: None,
+        max_clients: 8,
+    };
+    let addr = start(config, FixedSecret(42));
+
+    let mut ann = Client::connect(addr);
+    let mut bob = Client::connect(addr);
+    ann.read();
+    bob.read();
+    ann.send("NAME ann");
+    assert_eq!(ann.send("GUESS 1"), "LOSE");
+
+    // Coming back under the same name doesn't reset the attempts.
+    drop(ann);
+    let mut ann = Client::connect(addr);
+    ann.read();
+    ann.send("NAME ann");
+    assert_eq!(
+        ann.send("GUESS 42"),
+        "ERR out of attempts, wait for the next round"
+    );
+
+    assert_eq!(bob.send("GUESS 1"), "LOSE");
+    assert_eq!(ann.send("GUESS 42"), "OVER - 42");
+    assert_eq!(ann.read(), "HELLO 1 100 1");
+    assert_eq!(ann.send("GUESS 42"), "WIN 1");
+}
+
+#[test]
+fn full_servers_and_idle_clients_are_turned_away() {
+    let config = ServerConfig {
+        range: 1..=100,
+        max_attempts: None,
+        race: false,
+        idle_timeout: Some(Duration::from_millis(300)),
+        max_clients: 1,
+    };
+    let addr = start(config, FixedSecret(42));
+
+    let mut first = Client::connect(addr);
+    assert_eq!(first.read(), "HELLO 1 100");
+    let mut second = Client::connect(addr);
+    assert_eq!(second.read(), "ERR server full");
+
+    // Saying nothing for too long frees the place for someone else.
+    assert_eq!(first.read(), "ERR idle timeout");
+    assert_eq!(first.read(), "");
+    let mut third = Client::connect(addr);
+    assert_eq!(third.read(), "HELLO 1 100");
+}
@@ -0,0 +1,46 @@
+use guessing_game::distribution::Distribution;
+use guessing_game::simulate::Simulation;
+use guessing_game::solver::Strategy;
+
+#[test]
+fn simulation_is_reproducible_and_binary_search_stays_in_bound() {
+    let simulation = Simulation {
+        range: 1..=100,
+        max_attempts: None,
+        games: 500,
+        seed: 42,
+        distribution: Distribution::Uniform,
+    };
+
+    let binary = simulation.run(Strategy::Binary).unwrap();
+    assert_eq!(binary.games, 500);
+    assert_eq!(binary.wins, 500);
+    assert!(binary.worst <= 7);
+    assert_eq!(binary.histogram.values().sum::<u64>(), 500);
+
+    let random = simulation.run(Strategy::Random).unwrap();
+    assert_eq!(random, simulation.run(Strategy::Random).unwrap());
+}
+
+#[test]
+fn summary_matches_the_histogram() {
+    let simulation = Simulation {
+        range: 1..=10,
+        max_attempts: None,
+        games: 101,
+        seed: 7,
+        distribution: Distribution::Uniform,
+    };
+    let stats = simulation.run(Strategy::Linear).unwrap();
+
+    let mut attempts: Vec<u32> = stats
+        .histogram
+        .iter()
+        .flat_map(|(&a, &count)| std::iter::repeat_n(a, count as usize))
+        .collect();
+    attempts.sort_unstable();
+    assert_eq!(stats.median, f64::from(attempts[50]));
+    assert_eq!(stats.worst, *attempts.last().unwrap());
+    let mean = attempts.iter().map(|&a| f64::from(a)).sum::<f64>() / 101.0;
+    assert!((stats.mean - mean).abs() < 1e-9);
+}
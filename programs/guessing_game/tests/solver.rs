@@ -0,0 +1,45 @@
+use guessing_game::solver::{self, BinarySearch, Strategy};
+use guessing_game::{FixedSecret, Game, Outcome};
+
+#[test]
+fn binary_search_wins_within_worst_case_bound() {
+    for max in [1, 2, 3, 7, 8, 100, 128, 1000] {
+        let bound = BinarySearch::worst_case(max);
+
+        for secret in 1..=max {
+            let mut game = Game::new(1..=max, FixedSecret(secret));
+            let mut bot = BinarySearch::new(1..=max);
+            let transcript = solver::solve(&mut game, &mut bot).unwrap();
+
+            assert!(game.is_won());
+            assert!(
+                transcript.len() as u32 <= bound,
+                "secret {secret} of 1..={max}"
+            );
+        }
+    }
+}
+
+#[test]
+fn every_strategy_wins_with_consistent_feedback() {
+    for strategy in Strategy::ALL {
+        for secret in 1..=50 {
+            let mut game = Game::new(1..=50, FixedSecret(secret));
+            let mut bot = strategy.solver(1..=50, secret);
+            let transcript = solver::solve(&mut game, bot.as_mut()).unwrap();
+
+            assert_eq!(transcript.last().unwrap().outcome, Outcome::Correct);
+            assert_eq!(transcript.last().unwrap().guess, secret);
+        }
+    }
+}
+
+#[test]
+fn contradictory_feedback_is_detected() {
+    // The secret lies outside the range the bot was told about, so no answer
+    // it gets can ever be "correct".
+    let mut game = Game::new(1..=10, FixedSecret(10));
+    let mut bot = BinarySearch::new(1..=5);
+
+    assert!(solver::solve(&mut game, &mut bot).is_err());
+}
@@ -0,0 +1,83 @@
+use std::time::Duration;
+
+use guessing_game::timer::{self, Countdown, Expiry, ManualClock, TimeLimits};
+use guessing_game::{FixedSecret, Game};
+
+fn secs(n: u64) -> Duration {
+    Duration::from_secs(n)
+}
+
+#[test]
+fn slow_guesses_forfeit_an_attempt_each() {
+    let clock = ManualClock::new();
+    let limits = TimeLimits {
+        per_guess: Some(secs(10)),
+        per_game: None,
+    };
+    let mut countdown = Countdown::new(limits, &clock);
+    let mut game = Game::new(1..=100, FixedSecret(42)).with_max_attempts(2);
+
+    clock.advance(secs(9));
+    assert_eq!(countdown.remaining(), Some(secs(1)));
+    assert_eq!(countdown.enforce(&mut game), None);
+
+    clock.advance(secs(1));
+    assert_eq!(countdown.enforce(&mut game), Some(Expiry::Guess));
+    assert_eq!(game.attempts(), 1);
+    assert_eq!(countdown.remaining(), Some(secs(10)));
+
+    clock.advance(secs(10));
+    assert_eq!(countdown.enforce(&mut game), Some(Expiry::Guess));
+    assert!(game.is_lost());
+    assert!(!game.is_expired());
+}
+
+#[test]
+fn running_out_of_game_time_loses_the_game() {
+    let clock = ManualClock::new();
+    let limits = TimeLimits {
+        per_guess: Some(secs(10)),
+        per_game: Some(secs(15)),
+    };
+    let mut countdown = Countdown::new(limits, &clock);
+    let mut game = Game::new(1..=100, FixedSecret(42));
+
+    clock.advance(secs(8));
+    game.guess(50);
+    countdown.start_turn();
+    // The turn has 10s left but the game only 7s.
+    assert_eq!(countdown.remaining(), Some(secs(7)));
+
+    clock.advance(secs(20));
+    assert_eq!(countdown.enforce(&mut game), Some(Expiry::Game));
+    assert!(game.is_lost());
+    assert!(game.is_expired());
+    assert_eq!(game.attempts(), 1);
+    assert_eq!(countdown.elapsed(), secs(28));
+}
+
+#[test]
+fn limits_beyond_the_clock_never_run_out() {
+    let clock = ManualClock::new();
+    let limits = TimeLimits {
+        per_guess: Some(secs(10)),
+        per_game: Some(Duration::MAX),
+    };
+    let mut countdown = Countdown::new(limits, &clock);
+    let mut game = Game::new(1..=100, FixedSecret(42));
+
+    clock.advance(secs(4));
+    assert_eq!(countdown.remaining(), Some(secs(6)));
+    clock.advance(secs(6));
+    assert_eq!(countdown.enforce(&mut game), Some(Expiry::Guess));
+    assert!(!game.is_expired());
+}
+
+#[test]
+fn lines_arrive_over_a_channel_until_the_input_ends() {
+    let lines = timer::spawn_lines(&b"12\nquit\n"[..]);
+
+    assert_eq!(lines.recv_timeout(secs(5)).unwrap().unwrap(), "12");
+    assert_eq!(lines.recv_timeout(secs(5)).unwrap().unwrap(), "quit");
+    assert!(lines.recv_timeout(secs(5)).is_err());
+}
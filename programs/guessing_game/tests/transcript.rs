@@ -0,0 +1,76 @@
+use guessing_game::console;
+use guessing_game::hints::HintMode;
+use guessing_game::i18n::{Locale, Messages};
+use guessing_game::transcript::{Entry, Recorder, Setup, Transcript};
+use guessing_game::{Game, SeededSecret};
+
+fn record(game: &mut Game, lines: &[&str]) -> Vec<u8> {
+    record_in(Locale::En, game, lines)
+}
+
+fn record_in(lang: Locale, game: &mut Game, lines: &[&str]) -> Vec<u8> {
+    let setup = Setup::of(game, lang).unwrap();
+    let messages = Messages::new(lang);
+    let mut file = Vec::new();
+    let mut recorder = Recorder::new(&mut file, &setup).unwrap();
+    for line in lines {
+        let response = console::respond(game, line, &messages);
+        recorder.record(&Entry::new(line, &response)).unwrap();
+    }
+    file
+}
+
+#[test]
+fn recorded_games_replay_identically() {
+    let mut game = Game::new(1..=100, SeededSecret::new(7))
+        .with_hints([HintMode::Distance, HintMode::Clues])
+        .with_max_attempts(5);
+    let file = record(&mut game, &["50", "parity", "nonsense", "25", "0", "quit"]);
+
+    let transcript = Transcript::load(file.as_slice()).unwrap();
+    assert_eq!(transcript.setup.seed, 7);
+    assert_eq!(transcript.entries.len(), 6);
+    assert_eq!(transcript.entries[0].parsed, "guess 50");
+    assert_eq!(transcript.entries[1].parsed, "clue parity");
+    assert_eq!(transcript.entries[2].parsed, "invalid");
+
+    let steps = transcript.replay();
+    assert_eq!(steps.len(), 6);
+    assert!(steps.iter().all(|step| step.matches()));
+}
+
+#[test]
+fn replay_flags_responses_that_changed() {
+    let mut game = Game::new(1..=100, SeededSecret::new(7));
+    let file = record(&mut game, &["50", "25"]);
+
+    let mut transcript = Transcript::load(file.as_slice()).unwrap();
+    transcript.entries[1].response = vec!["You win!".to_string()];
+
+    let steps = transcript.replay();
+    assert!(steps[0].matches());
+    assert!(!steps[1].matches());
+    assert_ne!(steps[1].replayed.response, steps[1].recorded.response);
+}
+
+#[test]
+fn replay_answers_in_the_recorded_language() {
+    let mut game = Game::new(1..=100, SeededSecret::new(7));
+    let file = record_in(Locale::De, &mut game, &["50", "25"]);
+
+    let transcript = Transcript::load(file.as_slice()).unwrap();
+    assert_eq!(transcript.setup.lang, Locale::De);
+    let steps = transcript.replay();
+    assert!(steps.iter().all(|step| step.matches()));
+    assert_eq!(steps[0].replayed.response, ["Zu groß!"]);
+}
+
+#[test]
+fn malformed_transcripts_are_rejected() {
+    let no_start = br#"{"type":"input","line":"1","parsed":"guess 1","response":[]}"#;
+    assert!(Transcript::load(&no_start[..]).is_err());
+
+    let future = br#"{"type":"start","version":99,"seed":1,"min":1,"max":10,"max_attempts":null,"hints":[],"free_repeats":false}"#;
+    let err = Transcript::load(&future[..]).unwrap_err();
+    assert!(err.to_string().contains("version 99"), "{err}");
+}
@@ -0,0 +1,94 @@
+#![cfg(feature = "tui")]
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use guessing_game::i18n::{Locale, Messages};
+use guessing_game::tui::{NumberLine, Screen};
+use guessing_game::{FixedSecret, Game};
+
+fn press(screen: &mut Screen, code: KeyCode) -> bool {
+    screen.handle_key(KeyEvent::new(code, KeyModifiers::NONE))
+}
+
+#[test]
+fn arrows_nudge_the_entry_inside_the_range() {
+    let messages = Messages::new(Locale::En);
+    let mut game = Game::new(1..=20, FixedSecret(7));
+    let mut screen = Screen::new(&mut game, &messages);
+    assert_eq!(screen.entry(), "10");
+
+    press(&mut screen, KeyCode::Right);
+    assert_eq!(screen.entry(), "11");
+    press(&mut screen, KeyCode::Up);
+    assert_eq!(screen.entry(), "20");
+    press(&mut screen, KeyCode::Down);
+    press(&mut screen, KeyCode::Down);
+    press(&mut screen, KeyCode::Left);
+    assert_eq!(screen.entry(), "1");
+
+    press(&mut screen, KeyCode::Backspace);
+    press(&mut screen, KeyCode::Left);
+    assert_eq!(screen.entry(), "1");
+    assert!(!press(&mut screen, KeyCode::Esc));
+}
+
+#[test]
+fn enter_submits_the_entry() {
+    let messages = Messages::new(Locale::En);
+    let mut game = Game::new(1..=20, FixedSecret(7));
+    let mut screen = Screen::new(&mut game, &messages);
+
+    assert!(press(&mut screen, KeyCode::Enter));
+    assert_eq!(screen.history().len(), 1);
+    assert!(screen.history()[0].contains("Too big!"));
+    // The next guess starts from the middle of what's left.
+    assert_eq!(screen.entry(), "5");
+
+    press(&mut screen, KeyCode::Backspace);
+    press(&mut screen, KeyCode::Enter);
+    assert_eq!(screen.message(), messages.text("invalid_number"));
+
+    press(&mut screen, KeyCode::Char('7'));
+    press(&mut screen, KeyCode::Enter);
+    assert_eq!(screen.message(), "You win in 2 attempts! Press any key.");
+    drop(screen);
+    assert!(game.is_won());
+}
+
+#[test]
+fn number_line_cells_cover_the_range() {
+    let line = NumberLine::new(&(1..=100), 10);
+    assert_eq!(line.cells(), 10);
+    assert_eq!(line.covers(0), 1..=10);
+    assert_eq!(line.covers(9), 91..=100);
+    assert_eq!(line.cell_of(1), 0);
+    assert_eq!(line.cell_of(10), 0);
+    assert_eq!(line.cell_of(11), 1);
+    assert_eq!(line.cell_of(100), 9);
+
+    // Never more cells than numbers.
+    let line = NumberLine::new(&(5..=7), 40);
+    assert_eq!(line.cells(), 3);
+    assert_eq!(line.covers(2), 7..=7);
+    assert_eq!(line.cell_of(7), 2);
+}
+
+#[test]
+fn number_line_handles_the_full_u64_range() {
+    let line = NumberLine::new(&(0..=u64::MAX), 64);
+    assert_eq!(line.covers(0), 0..=(1 << 58) - 1);
+    assert_eq!(line.covers(63), 63 << 58..=u64::MAX);
+    assert_eq!(line.cell_of(0), 0);
+    assert_eq!(line.cell_of(u64::MAX), 63);
+
+    let line = NumberLine::new(&(1..=u64::MAX), 7);
+    assert_eq!(*line.covers(0).start(), 1);
+    assert_eq!(*line.covers(6).end(), u64::MAX);
+    for cell in 1..7 {
+        assert_eq!(
+            *line.covers(cell).start(),
+            line.covers(cell - 1).end() + 1,
+            "cell {cell}"
+        );
+    }
+    assert_eq!(line.cell_of(u64::MAX), 6);
+}
@@ -0,0 +1,131 @@
+use guessing_game::solver::{self, Solver};
+use guessing_game::ulam::{Liar, UlamSolver};
+use guessing_game::{FixedSecret, Game, Outcome};
+
+/// The most attempts `bot` can be made to take when the secret is any
+/// number from 1 to `lies.len()` and every answer may be a lie while the
+/// secret stays within `max_lies` of them. `lies` counts the lies each
+/// number implies so far.
+fn worst_liar(bot: &UlamSolver, lies: &[u32], max_lies: u32, attempts: u32) -> u32 {
+    let mut bot = bot.clone();
+    let guess = bot.next_guess().expect("the secret is still possible");
+    let index = guess as usize - 1;
+
+    let mut most = 0;
+    if lies.get(index).is_some_and(|&told| told <= max_lies) {
+        most = attempts + 1;
+    }
+    for outcome in [Outcome::TooSmall, Outcome::TooBig] {
+        let implied: Vec<u32> = (0..lies.len())
+            .map(|n| match n.cmp(&index) {
+                std::cmp::Ordering::Equal => max_lies + 1,
+                std::cmp::Ordering::Less if outcome == Outcome::TooSmall => lies[n] + 1,
+                std::cmp::Ordering::Greater if outcome == Outcome::TooBig => lies[n] + 1,
+                _ => lies[n],
+            })
+            .collect();
+        if implied.iter().all(|&told| told > max_lies) {
+            continue;
+        }
+        let mut answered = bot.clone();
+        answered.observe(guess, outcome);
+        most = most.max(worst_liar(&answered, &implied, max_lies, attempts + 1));
+    }
+    most
+}
+
+#[test]
+fn worst_case_holds_against_every_liar() {
+    for (max_lies, largest) in [(0, 64), (1, 64), (2, 40), (3, 16)] {
+        for max in 1..=largest {
+            let bot = UlamSolver::new(1..=max, max_lies);
+            let worst = worst_liar(&bot, &vec![0; max as usize], max_lies, 0);
+            assert!(
+                worst <= UlamSolver::worst_case(max, max_lies),
+                "1..={max} with {max_lies} lies took {worst}"
+            );
+        }
+    }
+}
+
+#[test]
+fn worst_case_is_berlekamps_volume_bound() {
+    // 100 numbers and one lie: 100 * (1 + 11) <= 2^11 but 100 * 11 > 2^10.
+    assert_eq!(UlamSolver::worst_case(100, 1), 11);
+    assert_eq!(UlamSolver::worst_case(1_000_000, 2), 29);
+    // Without lies it's plain binary search.
+    assert_eq!(UlamSolver::worst_case(100, 0), 7);
+    assert_eq!(UlamSolver::worst_case(1, 3), 1);
+}
+
+#[test]
+fn huge_ranges_are_solved_within_worst_case_bound() {
+    for max_lies in [1, 5] {
+        let liar = Liar::new(max_lies, 3).with_chance(0.5);
+        let secret = u64::MAX / 3;
+        let mut game = Game::new(1..=u64::MAX, FixedSecret(secret)).with_lies(liar);
+        let mut bot = UlamSolver::new(1..=u64::MAX, max_lies);
+        let transcript = solver::solve(&mut game, &mut bot).unwrap();
+
+        assert!(game.is_won());
+        assert!(transcript.len() as u32 <= UlamSolver::worst_case(u64::MAX, max_lies));
+    }
+}
+
+#[test]
+fn ulam_solver_wins_within_worst_case_bound() {
+    for max in [1, 2, 3, 7, 8, 100, 1000] {
+        for max_lies in 0..=3 {
+            let bound = UlamSolver::worst_case(max, max_lies);
+
+            for secret in (1..=max).step_by((max as usize / 50).max(1)) {
+                for (seed, chance) in [(1, 1.0), (2, 0.5), (3, 0.25), (4, 0.0)] {
+                    let liar = Liar::new(max_lies, seed + secret).with_chance(chance);
+                    let mut game = Game::new(1..=max, FixedSecret(secret)).with_lies(liar);
+                    let mut bot = UlamSolver::new(1..=max, max_lies);
+                    let transcript = solver::solve(&mut game, &mut bot).unwrap();
+
+                    assert!(game.is_won(), "secret {secret} of 1..={max}");
+                    assert!(
+                        transcript.len() as u32 <= bound,
+                        "secret {secret} of 1..={max} with {max_lies} lies"
+                    );
+                    assert!(game.lies().len() as u32 <= max_lies);
+                    assert_eq!(bot.lies_found() as usize, game.lies().len());
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn lies_are_recorded_and_revealed_by_position() {
+    let liar = Liar::new(2, 0).with_chance(1.0);
+    let mut game = Game::new(1..=100, FixedSecret(40)).with_lies(liar);
+
+    assert_eq!(game.guess(50), Outcome::TooSmall);
+    assert_eq!(game.guess(30), Outcome::TooBig);
+    assert_eq!(game.guess(60), Outcome::TooBig);
+    assert_eq!(game.lies(), [0, 1]);
+    assert_eq!(game.max_lies(), Some(2));
+}
+
+#[test]
+fn correct_guesses_are_never_lies() {
+    let liar = Liar::new(3, 0).with_chance(1.0);
+    let mut game = Game::new(1..=100, FixedSecret(40)).with_lies(liar);
+
+    assert_eq!(game.guess(40), Outcome::Correct);
+    assert!(game.lies().is_empty());
+}
+
+#[test]
+fn repeated_guesses_are_not_ruled_out_when_answers_may_lie() {
+    let liar = Liar::new(1, 0).with_chance(0.0);
+    let mut game = Game::new(1..=100, FixedSecret(40)).with_lies(liar);
+
+    assert_eq!(game.guess(50), Outcome::TooBig);
+    assert_eq!(game.guess(50), Outcome::TooBig);
+    assert_eq!(game.guess(70), Outcome::TooBig);
+    assert_eq!(game.attempts(), 3);
+}
@@ -0,0 +1,80 @@
+use guessing_game::console::{self, Input};
+use guessing_game::daily::Date;
+use guessing_game::hints::HintMode;
+use guessing_game::i18n::Messages;
+use guessing_game::{Game, SeededSecret, words};
+
+#[test]
+fn the_word_list_is_sorted_lowercase_and_unique() {
+    let list = words::list();
+    assert!(list.len() > 100);
+    assert!(list.windows(2).all(|pair| pair[0] < pair[1]));
+    assert!(
+        list.iter()
+            .all(|word| word.bytes().all(|b| b.is_ascii_lowercase()))
+    );
+
+    assert_eq!(words::lookup(" Zebra "), Some("zebra"));
+    assert_eq!(words::lookup("xyzzy"), None);
+    assert_eq!(words::next("zebra"), Some("zipper"));
+    assert_eq!(words::previous("able"), None);
+}
+
+#[test]
+fn word_games_use_the_same_console() {
+    let messages = Messages::default();
+    let mut game = Game::from_candidates(words::list().iter().copied(), SeededSecret::new(4));
+    let secret = game.secret();
+
+    let miss = console::respond(&mut game, "xyzzy", &messages);
+    assert_eq!(
+        miss.lines,
+        ["That's not in the word list, try another word!"]
+    );
+    // Clues only make sense for numbers.
+    let clue = console::respond(&mut game, "parity", &messages);
+    assert!(matches!(clue.input, Input::Invalid(_)), "{:?}", clue.input);
+    assert_eq!(game.attempts(), 0);
+
+    let hit = console::respond(&mut game, secret, &messages);
+    assert_eq!(hit.input, Input::Guess(secret));
+    assert_eq!(hit.lines, ["You win!"]);
+
+    // The seed picks the same word again.
+    let replay = Game::from_candidates(words::list().iter().copied(), SeededSecret::new(4));
+    assert_eq!(replay.secret(), secret);
+}
+
+#[test]
+fn words_and_dates_get_interval_and_warmer_hints() {
+    let messages = Messages::default();
+    let mut game =
+        Game::from_candidates(["apple", "bee", "cat", "dog", "egg"], SeededSecret::new(1))
+            .with_hints([HintMode::Interval, HintMode::WarmerColder]);
+    let (first, second) = match game.secret() {
+        "apple" | "bee" => ("egg", "dog"),
+        _ => ("apple", "bee"),
+    };
+
+    let hints = console::respond(&mut game, first, &messages).lines;
+    assert_eq!(hints.len(), 2, "{hints:?}");
+    assert!(hints[1].starts_with("The secret is between "), "{hints:?}");
+    let hints = console::respond(&mut game, second, &messages).lines;
+    if second != game.secret() {
+        assert_eq!(hints.last().unwrap(), "Warmer!", "{hints:?}");
+    }
+
+    let days = Date::new(2024, 1, 1).unix_days()..=Date::new(2024, 1, 31).unix_days();
+    let mut game = Game::from_candidates(days.map(Date::from_unix_days), SeededSecret::new(2))
+        .with_hints([HintMode::Interval]);
+    let guess = if game.secret() == Date::new(2024, 1, 1) {
+        "2024-01-31"
+    } else {
+        "2024-01-01"
+    };
+    let lines = console::respond(&mut game, guess, &messages).lines;
+    assert!(
+        lines[1].starts_with("The secret is between 2024-"),
+        "{lines:?}"
+    );
+}